@@ -0,0 +1,36 @@
+use syn::{
+    parse::{Parse, ParseStream},
+    LitStr, Path, Token,
+};
+
+use crate::LitStrMaybe;
+
+/// Arguments to the `checked_id!` macro.
+///
+/// ```rust,ignore
+/// checked_id!(crate::ids::MyId, "web_server")
+/// ```
+pub(crate) struct CheckedIdArgs {
+    /// Path to the ID type, e.g. `crate::ids::MyId`.
+    pub ty_path: Path,
+    /// The proposed ID string.
+    pub proposed_id: LitStrMaybe,
+}
+
+impl Parse for CheckedIdArgs {
+    fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+        let ty_path = input.parse::<Path>()?;
+        let mut proposed_id = LitStrMaybe(None);
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+            if input.peek(LitStr) {
+                proposed_id = LitStrMaybe(Some(input.parse::<LitStr>()?));
+            }
+        }
+
+        Ok(CheckedIdArgs {
+            ty_path,
+            proposed_id,
+        })
+    }
+}
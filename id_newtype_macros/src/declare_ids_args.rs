@@ -0,0 +1,93 @@
+use syn::{
+    parse::{Parse, ParseStream},
+    Attribute, Ident, LitStr, Path, Token, Visibility,
+};
+
+/// Arguments to the `declare_ids!` macro.
+///
+/// ```rust,ignore
+/// declare_ids! {
+///     crate::ids::K8sName;
+///
+///     /// The web server.
+///     pub const WEB = "web";
+///     pub const DB = "db";
+///
+///     /// All services.
+///     pub(crate) const ALL_SERVICES;
+/// }
+/// ```
+pub(crate) struct DeclareIdsArgs {
+    /// Path to the ID type, e.g. `crate::ids::MyId`.
+    pub ty_path: Path,
+    /// The constants to declare.
+    pub id_consts: Vec<IdConst>,
+    /// The constant for the slice of all IDs, which has no ID string.
+    pub all_const: Option<AllConst>,
+}
+
+/// A constant in `declare_ids!`, e.g. `pub const WEB = "web";`.
+pub(crate) struct IdConst {
+    /// Attributes on the constant, such as doc comments.
+    pub attrs: Vec<Attribute>,
+    /// Visibility of the constant.
+    pub vis: Visibility,
+    /// Name of the constant.
+    pub name: Ident,
+    /// The proposed ID string.
+    pub proposed_id: LitStr,
+}
+
+/// The constant for the slice of all IDs in `declare_ids!`, e.g. `pub const
+/// ALL;`.
+pub(crate) struct AllConst {
+    /// Attributes on the constant, such as doc comments.
+    pub attrs: Vec<Attribute>,
+    /// Visibility of the constant.
+    pub vis: Visibility,
+    /// Name of the constant.
+    pub name: Ident,
+}
+
+impl Parse for DeclareIdsArgs {
+    fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+        let ty_path = input.parse::<Path>()?;
+        input.parse::<Token![;]>()?;
+
+        let mut id_consts = Vec::new();
+        let mut all_const = None;
+        while !input.is_empty() {
+            let attrs = input.call(Attribute::parse_outer)?;
+            let vis = input.parse::<Visibility>()?;
+            input.parse::<Token![const]>()?;
+            let name = input.parse::<Ident>()?;
+
+            if input.peek(Token![;]) {
+                input.parse::<Token![;]>()?;
+                if all_const.is_some() {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        "Duplicate constant for all IDs, only one `const NAME;` may be declared.",
+                    ));
+                }
+                all_const = Some(AllConst { attrs, vis, name });
+            } else {
+                input.parse::<Token![=]>()?;
+                let proposed_id = input.parse::<LitStr>()?;
+                input.parse::<Token![;]>()?;
+                id_consts.push(IdConst {
+                    attrs,
+                    vis,
+                    name,
+                    proposed_id,
+                });
+            }
+        }
+
+        Ok(DeclareIdsArgs {
+            ty_path,
+            id_consts,
+            all_const,
+        })
+    }
+}
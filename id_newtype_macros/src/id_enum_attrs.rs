@@ -0,0 +1,44 @@
+use syn::{
+    parse::{Parse, ParseStream},
+    Attribute, Path,
+};
+
+/// The `#[id_enum(..)]` attribute on an enum for `#[derive(IdEnum)]`.
+///
+/// ```rust,ignore
+/// #[id_enum(crate::ids::K8sName)]
+/// #[id_enum(crate::ids::K8sName<'s>)]
+/// ```
+pub(crate) struct IdEnumAttrs {
+    /// Path to the ID type, e.g. `crate::ids::MyId`, with the enum's lifetime
+    /// if the ID type has one.
+    pub ty_path: Path,
+}
+
+impl IdEnumAttrs {
+    /// Returns the `#[id_enum(..)]` attribute, if any.
+    pub fn from_attrs(attrs: &[Attribute]) -> syn::parse::Result<Option<Self>> {
+        let mut id_enum_attrs = attrs.iter().filter(|attr| attr.path().is_ident("id_enum"));
+
+        let id_enum_attr = id_enum_attrs
+            .next()
+            .map(Attribute::parse_args)
+            .transpose()?;
+        if let Some(attr) = id_enum_attrs.next() {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "Duplicate `#[id_enum(..)]` attribute.",
+            ));
+        }
+
+        Ok(id_enum_attr)
+    }
+}
+
+impl Parse for IdEnumAttrs {
+    fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+        let ty_path = input.parse::<Path>()?;
+
+        Ok(IdEnumAttrs { ty_path })
+    }
+}
@@ -0,0 +1,117 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    Attribute, Ident, Token,
+};
+
+/// Options in `#[id_newtype(..)]` attributes for `#[derive(IdNewtype)]`.
+///
+/// ```rust,ignore
+/// #[id_newtype(error = MyIdInvalidFmt, macro = my_id)]
+/// #[id_newtype(first = "a-z", rest = "a-z0-9-")]
+/// ```
+///
+/// Options other than `error` and `macro` are grammar options, which are
+/// forwarded to `id_newtype!`, where their values are checked.
+#[derive(Default)]
+pub(crate) struct IdNewtypeAttrs {
+    /// Name of the invalid value error type.
+    pub error: Option<Ident>,
+    /// Name of the compile time checked macro.
+    pub macro_name: Option<Ident>,
+    /// Grammar options, e.g. `first = "a-z", rest = "a-z0-9-",`.
+    pub rules: TokenStream,
+}
+
+impl IdNewtypeAttrs {
+    /// Returns the options from all `#[id_newtype(..)]` attributes.
+    pub fn from_attrs(attrs: &[Attribute]) -> syn::parse::Result<Self> {
+        let mut id_newtype_attrs = Self::default();
+        for attr in attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("id_newtype"))
+        {
+            let Self {
+                error,
+                macro_name,
+                rules,
+            } = attr.parse_args::<Self>()?;
+
+            if let Some(error) = error {
+                if id_newtype_attrs.error.is_some() {
+                    return Err(syn::Error::new(error.span(), "Duplicate `error` option."));
+                }
+                id_newtype_attrs.error = Some(error);
+            }
+            if let Some(macro_name) = macro_name {
+                if id_newtype_attrs.macro_name.is_some() {
+                    return Err(syn::Error::new(
+                        macro_name.span(),
+                        "Duplicate `macro` option.",
+                    ));
+                }
+                id_newtype_attrs.macro_name = Some(macro_name);
+            }
+            id_newtype_attrs.rules.extend(rules);
+        }
+
+        Ok(id_newtype_attrs)
+    }
+}
+
+impl IdNewtypeAttrs {
+    /// Grammar options accepted by `id_newtype!`.
+    const GRAMMAR_OPTIONS: &[&str] = &[
+        "first",
+        "rest",
+        "min_len",
+        "max_len",
+        "reserved",
+        "reserved_prefixes",
+        "reserved_sets",
+        "style",
+        "predicate",
+    ];
+}
+
+impl Parse for IdNewtypeAttrs {
+    fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+        let mut id_newtype_attrs = Self::default();
+
+        while !input.is_empty() {
+            // `macro` is a keyword, so it is not parsed by `Ident::parse`.
+            let key = input.call(Ident::parse_any)?;
+            match key.to_string().as_str() {
+                "error" => {
+                    input.parse::<Token![=]>()?;
+                    id_newtype_attrs.error = Some(input.parse::<Ident>()?);
+                }
+                "macro" => {
+                    input.parse::<Token![=]>()?;
+                    id_newtype_attrs.macro_name = Some(input.parse::<Ident>()?);
+                }
+                key_str if !Self::GRAMMAR_OPTIONS.contains(&key_str) => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!("Unknown `id_newtype` option: `{key}`."),
+                    ));
+                }
+                _ => {
+                    let mut value = TokenStream::new();
+                    while !input.is_empty() && !input.peek(Token![,]) {
+                        value.extend([input.parse::<proc_macro2::TokenTree>()?]);
+                    }
+                    id_newtype_attrs.rules.extend(quote!(#key #value,));
+                }
+            }
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(id_newtype_attrs)
+    }
+}
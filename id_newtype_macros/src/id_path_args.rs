@@ -0,0 +1,52 @@
+use syn::{
+    parse::{Parse, ParseStream},
+    Ident, LitChar, LitStr, Path, Token,
+};
+
+/// Arguments to the `id_path!` macro.
+///
+/// ```rust,ignore
+/// id_path!(crate::ids::MyId, "us_east.cluster_1")
+/// id_path!(crate::ids::K8sName, "us-east/cluster-1"; separator = '/')
+/// ```
+pub(crate) struct IdPathArgs {
+    /// Path to the segment ID type, e.g. `crate::ids::MyId`.
+    pub ty_path: Path,
+    /// The proposed path string.
+    pub proposed_path: LitStr,
+    /// Separator between segments, which follows the `;` and defaults to `.`.
+    pub separator: Option<LitChar>,
+}
+
+impl Parse for IdPathArgs {
+    fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+        let ty_path = input.parse::<Path>()?;
+        input.parse::<Token![,]>()?;
+        let proposed_path = input.parse::<LitStr>()?;
+
+        let separator = if input.is_empty() {
+            None
+        } else {
+            input.parse::<Token![;]>()?;
+            let key = input.parse::<Ident>()?;
+            if key != "separator" {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("Unknown `id_path` option: `{key}`."),
+                ));
+            }
+            input.parse::<Token![=]>()?;
+            let separator = input.parse::<LitChar>()?;
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            }
+            Some(separator)
+        };
+
+        Ok(IdPathArgs {
+            ty_path,
+            proposed_path,
+            separator,
+        })
+    }
+}
@@ -0,0 +1,38 @@
+use syn::{
+    parse::{Parse, ParseStream},
+    LitStr, Path, Token,
+};
+
+/// Arguments to the `ids!` macro.
+///
+/// ```rust,ignore
+/// ids!(crate::ids::MyId; "web", "db", "cache")
+/// ```
+pub(crate) struct IdsArgs {
+    /// Path to the ID type, e.g. `crate::ids::MyId`.
+    pub ty_path: Path,
+    /// The proposed ID strings.
+    pub proposed_ids: Vec<LitStr>,
+}
+
+impl Parse for IdsArgs {
+    fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+        let ty_path = input.parse::<Path>()?;
+        input.parse::<Token![;]>()?;
+
+        let mut proposed_ids = Vec::new();
+        while input.peek(LitStr) {
+            proposed_ids.push(input.parse::<LitStr>()?);
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            } else {
+                break;
+            }
+        }
+
+        Ok(IdsArgs {
+            ty_path,
+            proposed_ids,
+        })
+    }
+}
@@ -1,13 +1,32 @@
-use proc_macro2::Span;
-use quote::quote;
-use syn::{parse_macro_input, Ident};
+use proc_macro2::{Punct, Spacing, Span};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+    ext::IdentExt, parse_macro_input, spanned::Spanned, Data, DeriveInput, Fields, GenericArgument,
+    GenericParam, Ident, Lifetime, LitChar, LitStr, Path, PathArguments, Type,
+};
 
-use self::lit_str_maybe::LitStrMaybe;
+use self::{
+    checked_id_args::CheckedIdArgs,
+    declare_ids_args::{AllConst, DeclareIdsArgs, IdConst},
+    id_enum_attrs::IdEnumAttrs,
+    id_newtype_attrs::IdNewtypeAttrs,
+    id_path_args::IdPathArgs,
+    ids_args::IdsArgs,
+    lit_str_maybe::LitStrMaybe,
+};
 
+mod checked_id_args;
+mod declare_ids_args;
+mod id_enum_attrs;
+mod id_newtype_attrs;
+mod id_path_args;
+mod ids_args;
 mod lit_str_maybe;
 
 /// Returns an `Id` validated at compile time.
 ///
+/// This is `checked_id!(Id, "..")`, for an `Id` type in scope.
+///
 /// # Examples
 ///
 /// Instantiate a valid `Id` at compile time:
@@ -25,13 +44,12 @@ mod lit_str_maybe;
 ///
 /// If the ID is invalid, a compilation error is produced:
 ///
-/// ```rust,compile_fail
+/// ```rust,ignore
 /// use id_newtype::id;
 ///
 /// let _my_id: Id = id!("-invalid_id"); // Compile error
 /// //               ^^^^^^^^^^^^^^^^^^
-/// // error: "-invalid_id" is not a valid `Id`.
-/// //        `Id`s must begin with a letter or underscore, and contain only letters, numbers, or underscores.
+/// // error[E0080]: evaluation panicked: `-invalid_id` is not a valid `Id`: invalid first character `-` at column 1.
 /// #
 /// # struct Id(&'static str);
 /// # impl Id {
@@ -40,159 +58,1041 @@ mod lit_str_maybe;
 /// ```
 #[proc_macro]
 pub fn id(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    ensure_valid_id(&parse_macro_input!(input as LitStrMaybe), "Id", None).into()
+    let ty_path = Path::from(Ident::new("Id", Span::call_site()));
+    let proposed_id = parse_macro_input!(input as LitStrMaybe);
+    const_checked_id(Span::call_site(), &ty_path, proposed_id.as_ref()).into()
 }
 
-fn ensure_valid_id(
-    proposed_id: &LitStrMaybe,
-    ty_name: &str,
-    default: Option<&str>,
-) -> proc_macro2::TokenStream {
-    let proposed_id = proposed_id.as_ref().map(|lit_str| lit_str.value());
-    let proposed_id = proposed_id.as_deref().or(default);
-
-    if let Some(proposed_id) = proposed_id {
-        if is_valid_id(proposed_id) {
-            let ty_name = Ident::new(ty_name, Span::call_site());
-            quote!( #ty_name ::new_unchecked( #proposed_id ))
-        } else {
-            let message = format!(
-                "\"{proposed_id}\" is not a valid `{ty_name}`.\n\
-                `{ty_name}`s must begin with a letter or underscore, and contain only letters, numbers, or underscores."
+/// Returns an ID of the given type, validated at compile time.
+///
+/// The first argument is the path to the ID type, and the second argument is
+/// the ID string. The type must have `const fn validate(&str)` and `const fn
+/// new_unchecked(&'static str)` functions, which are generated by
+/// `id_newtype!`.
+///
+/// The ID is checked with the type's `validate` in a `const` block, so the
+/// type's own rules are used, and an invalid ID is a compile error.
+///
+/// `id_newtype!` generates a `macro_rules!` wrapper around this macro when it
+/// is passed a macro name, so most users will not need to call this directly.
+///
+/// # Examples
+///
+/// Instantiate a valid ID at compile time:
+///
+/// ```rust,ignore
+/// use id_newtype::checked_id;
+///
+/// let _my_id = checked_id!(crate::ids::MyId, "valid_id"); // Ok!
+/// ```
+///
+/// If the ID is invalid, a compilation error is produced:
+///
+/// ```rust,ignore
+/// use id_newtype::checked_id;
+///
+/// let _my_id = checked_id!(ids::MyId, "-invalid_id"); // Compile error
+/// //           ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+/// // error[E0080]: evaluation panicked: `-invalid_id` is not a valid `MyId`: invalid first character `-` at column 1.
+/// #
+/// # mod ids {
+/// #     pub struct MyId(&'static str);
+/// #     impl MyId {
+/// #         pub fn new_unchecked(s: &'static str) -> Self { Self(s) }
+/// #     }
+/// # }
+/// ```
+#[proc_macro]
+pub fn checked_id(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let CheckedIdArgs {
+        ty_path,
+        proposed_id,
+    } = parse_macro_input!(input as CheckedIdArgs);
+    const_checked_id(Span::call_site(), &ty_path, proposed_id.as_ref()).into()
+}
+
+/// Returns an array of IDs of the given type, each validated at compile time.
+///
+/// The first argument is the path to the ID type, followed by `;` and the ID
+/// strings. Each ID is checked with the type's `validate`, as for
+/// `checked_id!`.
+///
+/// A compile error is produced for each invalid ID, and for each ID that is
+/// already in the list.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use id_newtype::ids;
+///
+/// const SERVICES: [MyId; 3] = ids!(MyId; "web", "db", "cache"); // Ok!
+/// ```
+///
+/// ```rust,ignore
+/// use id_newtype::ids;
+///
+/// let _my_ids = ids!(MyId; "web", "db", "web"); // Compile error
+/// //                                        ^^^^^
+/// // error: `web` is a duplicate `MyId`, it is already in this list.
+/// #
+/// # struct MyId(&'static str);
+/// # impl MyId {
+/// #     const fn new_unchecked(s: &'static str) -> Self { Self(s) }
+/// # }
+/// ```
+#[proc_macro]
+pub fn ids(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let IdsArgs {
+        ty_path,
+        proposed_ids,
+    } = parse_macro_input!(input as IdsArgs);
+
+    let ids = checked_id_list(&ty_path, &proposed_ids);
+    quote!([#(#ids),*]).into()
+}
+
+/// Declares `const` IDs of the given type, each validated at compile time,
+/// and optionally a constant with the slice of every declared ID.
+///
+/// The first line is the path to the ID type, and ends with `;`. Each constant
+/// is declared as `const NAME = "id";`, and may have attributes and a
+/// visibility. Each ID is checked with the type's `validate`, as for
+/// `checked_id!`.
+///
+/// A constant without an ID, e.g. `pub(crate) const ALL_SERVICES;`, is
+/// declared as a `&[MyId]` slice with every declared ID, with its own
+/// attributes and visibility. At most one may be declared.
+///
+/// A compile error is produced for each invalid ID, and for each ID that is
+/// already declared.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use id_newtype::declare_ids;
+///
+/// declare_ids! {
+///     crate::ids::K8sName;
+///
+///     /// The web server.
+///     pub const WEB = "web-server";
+///     pub const DB = "db";
+///
+///     pub(crate) const ALL_SERVICES;
+/// }
+///
+/// assert_eq!(&[WEB, DB], ALL_SERVICES);
+/// ```
+#[proc_macro]
+pub fn declare_ids(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let DeclareIdsArgs {
+        ty_path,
+        id_consts,
+        all_const,
+    } = parse_macro_input!(input as DeclareIdsArgs);
+
+    let proposed_ids = id_consts
+        .iter()
+        .map(|id_const| id_const.proposed_id.clone())
+        .collect::<Vec<_>>();
+    let ids = checked_id_list(&ty_path, &proposed_ids);
+    let consts = id_consts.iter().zip(ids).map(|(id_const, id)| {
+        let IdConst {
+            attrs, vis, name, ..
+        } = id_const;
+        quote! {
+            #(#attrs)*
+            #vis const #name: #ty_path = #id;
+        }
+    });
+    let all_const = all_const.map(|AllConst { attrs, vis, name }| {
+        let names = id_consts.iter().map(|id_const| &id_const.name);
+        let doc = attrs
+            .is_empty()
+            .then(|| quote!(#[doc = "All IDs declared in this `declare_ids!` block."]));
+        quote! {
+            #doc
+            #(#attrs)*
+            #vis const #name: &[#ty_path] = &[#(#names),*];
+        }
+    });
+
+    quote! {
+        #(#consts)*
+        #all_const
+    }
+    .into()
+}
+
+/// Returns an `IdPath` of the given segment type, with each segment validated
+/// at compile time.
+///
+/// The first argument is the path to the segment ID type, and the second
+/// argument is the path string. A `separator = '/'` option may follow a `;`,
+/// and defaults to `.`.
+///
+/// Each segment is checked with the segment type's `validate`, and the
+/// separator with its `RULES`, as for `checked_id!`. A compile error is
+/// produced for each invalid segment, and if the separator is allowed in the
+/// segment type.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use id_newtype::id_path;
+///
+/// let node = id_path!(MyId, "us_east.cluster_1.node_1"); // Ok!
+/// let node = id_path!(MyId, "us_east/cluster_1"; separator = '/'); // Ok!
+/// ```
+///
+/// ```rust,ignore
+/// use id_newtype::id_path;
+///
+/// let _node = id_path!(MyId, "us_east..node_1"); // Compile error
+/// //                         ^^^^^^^^^^^^^^^^^
+/// // error[E0080]: evaluation panicked: `` is not a valid `MyId`: the value is empty.
+/// #
+/// # #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// # struct MyId(&'static str);
+/// # impl MyId {
+/// #     const fn new_unchecked(s: &'static str) -> Self { Self(s) }
+/// # }
+/// ```
+#[proc_macro]
+pub fn id_path(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let id_path_args = parse_macro_input!(input as IdPathArgs);
+    checked_id_path(&id_path_args).into()
+}
+
+/// Returns the construction of the `IdPath`, with the separator and each
+/// segment checked against the segment type's `RULES` and `validate` in
+/// `const` blocks.
+fn checked_id_path(id_path_args: &IdPathArgs) -> proc_macro2::TokenStream {
+    let IdPathArgs {
+        ty_path,
+        proposed_path,
+        separator,
+    } = id_path_args;
+    let separator = separator
+        .clone()
+        .unwrap_or_else(|| LitChar::new('.', proposed_path.span()));
+    let separator_char = separator.value();
+    let ty_name = ty_name(ty_path);
+    let message =
+        format!("`{separator_char}` cannot separate `{ty_name}`s, as it is allowed in them.");
+    let separator_check = quote_spanned! {separator.span()=>
+        const {
+            assert!(
+                !<#ty_path>::RULES.first_class().contains(#separator)
+                    && !<#ty_path>::RULES.rest_class().contains(#separator),
+                #message
             );
-            compile_fail(message)
         }
+    };
+
+    let span = proposed_path.span();
+    let segments = proposed_path
+        .value()
+        .split(separator_char)
+        .map(|segment| const_checked_id(span, ty_path, Some(&LitStr::new(segment, span))))
+        .collect::<Vec<_>>();
+
+    quote! {
+        {
+            #separator_check
+            ::id_newtype::IdPath::<#ty_path, #separator>::new_unchecked(
+                ::std::vec![#(#segments),*]
+            )
+        }
+    }
+}
+
+/// Returns the construction of each ID, checked with the ID type's `validate`
+/// in a `const` block, or a compile error at the ID if it is a duplicate of an
+/// earlier ID.
+fn checked_id_list(ty_path: &Path, proposed_ids: &[LitStr]) -> Vec<proc_macro2::TokenStream> {
+    proposed_ids
+        .iter()
+        .enumerate()
+        .map(|(index, proposed_id)| {
+            duplicate_id_error(ty_path, &proposed_ids[..index], proposed_id)
+                .unwrap_or_else(|| const_checked_id(proposed_id.span(), ty_path, Some(proposed_id)))
+        })
+        .collect()
+}
+
+/// Returns the unchecked construction of each ID, or a compile error at the
+/// ID if it is a duplicate of an earlier ID.
+///
+/// The IDs must be checked separately, e.g. with [`const_id_check`].
+fn unique_id_list(ty_path: &Path, proposed_ids: &[LitStr]) -> Vec<proc_macro2::TokenStream> {
+    proposed_ids
+        .iter()
+        .enumerate()
+        .map(|(index, proposed_id)| {
+            duplicate_id_error(ty_path, &proposed_ids[..index], proposed_id)
+                .unwrap_or_else(|| quote!( #ty_path ::new_unchecked( #proposed_id )))
+        })
+        .collect()
+}
+
+/// Returns a compile error at the ID if it is in `earlier_ids`.
+fn duplicate_id_error(
+    ty_path: &Path,
+    earlier_ids: &[LitStr],
+    proposed_id: &LitStr,
+) -> Option<proc_macro2::TokenStream> {
+    let value = proposed_id.value();
+    let is_duplicate = earlier_ids
+        .iter()
+        .any(|earlier_id| earlier_id.value() == value);
+
+    is_duplicate.then(|| {
+        let ty_name = ty_name(ty_path);
+        compile_fail(
+            proposed_id.span(),
+            format!("`{value}` is a duplicate `{ty_name}`, it is already in this list."),
+        )
+    })
+}
+
+/// Implements an ID newtype for a tuple struct with a single `Cow<'_, str>`
+/// field.
+///
+/// This generates the same items as `id_newtype!`, reading the lifetime from
+/// the struct. Options are passed in `#[id_newtype(..)]` attributes:
+///
+/// * `error = MyIdInvalidFmt`: Name of the invalid value error type. Defaults
+///   to the struct name followed by `InvalidFmt`.
+/// * `macro = my_id`: Name of the compile time checked macro, which is
+///   crate-local. No macro is generated if this is not set.
+/// * Grammar options, e.g. `first = "a-z"`, which are passed to `id_newtype!`.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use std::borrow::Cow;
+///
+/// use id_newtype::IdNewtype;
+///
+/// #[derive(Clone, Debug, Hash, PartialEq, Eq, IdNewtype)]
+/// #[id_newtype(error = MyIdInvalidFmt, macro = my_id)]
+/// pub struct MyId<'s>(Cow<'s, str>);
+/// ```
+#[proc_macro_derive(IdNewtype, attributes(id_newtype))]
+pub fn derive_id_newtype(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    id_newtype_impl(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn id_newtype_impl(input: &DeriveInput) -> syn::parse::Result<proc_macro2::TokenStream> {
+    let IdNewtypeAttrs {
+        error,
+        macro_name,
+        rules,
+    } = IdNewtypeAttrs::from_attrs(&input.attrs)?;
+
+    let lifetime = generic_lifetime(input, "IdNewtype")?;
+    let field_ty = match &input.data {
+        Data::Struct(data_struct) => match &data_struct.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+            _ => {
+                return Err(syn::Error::new(
+                    data_struct.fields.span(),
+                    "`IdNewtype` can only be derived for tuple structs with a single field, \
+                    e.g. `struct MyId(Cow<'static, str>);`.",
+                ));
+            }
+        },
+        Data::Enum(_) | Data::Union(_) => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "`IdNewtype` can only be derived for tuple structs with a single field, \
+                e.g. `struct MyId(Cow<'static, str>);`.",
+            ));
+        }
+    };
+    if lifetime.is_some() {
+        ensure_cow_str(field_ty, lifetime)?;
+    }
+
+    let ty_name = &input.ident;
+    let ty_err_name = error.unwrap_or_else(|| format_ident!("{ty_name}InvalidFmt"));
+    let dollar = Punct::new('$', Spacing::Alone);
+    let macro_impl = macro_name.as_ref().map(|macro_name| {
+        quote! {
+            ::id_newtype::id_newtype!(MACRO; (#dollar) #ty_name, #macro_name);
+        }
+    });
+
+    let tokens = match lifetime {
+        Some(lifetime) => quote! {
+            ::id_newtype::id_newtype!(NEW_LT; #ty_name, #ty_err_name, #lifetime, [#macro_name]);
+            ::id_newtype::id_newtype!(IMPL_LT; #ty_name, #ty_err_name, #lifetime, [#rules]);
+            #macro_impl
+        },
+        // `Cow<'static, str>` fields can be constructed in `const`s.
+        None if is_cow_str(field_ty, None) => quote! {
+            ::id_newtype::id_newtype!(NEW; #ty_name, #ty_err_name, [#macro_name]);
+            ::id_newtype::id_newtype!(IMPL; #ty_name, #ty_err_name, #field_ty, [#rules]);
+            #macro_impl
+        },
+        None => quote! {
+            ::id_newtype::id_newtype!(NEW_STORAGE; #ty_name, #ty_err_name, #field_ty, [#macro_name]);
+            ::id_newtype::id_newtype!(IMPL; #ty_name, #ty_err_name, #field_ty, [#rules]);
+            #macro_impl
+        },
+    };
+
+    Ok(tokens)
+}
+
+/// Maps the variants of an enum to well known IDs of an ID type.
+///
+/// The ID type is passed in an `#[id_enum(..)]` attribute on the enum, with
+/// the enum's lifetime if the ID type has one.
+///
+/// Each unit variant maps to the ID in its `#[id_enum("..")]` attribute, or
+/// the variant name in `snake_case` if there is none. One tuple variant with a
+/// single field of the ID type, e.g. `Other(MyId)`, may be used for all other
+/// IDs.
+///
+/// This generates:
+///
+/// * `From<Enum> for MyId`
+/// * `TryFrom<&MyId> for Enum`, which returns the ID as the error if it is not
+///   one of the unit variants.
+/// * `From<MyId> for Enum`, if there is an `Other(MyId)` variant.
+/// * `Enum::VARIANTS`, with each unit variant.
+///
+/// Each ID is checked with the ID type's `validate` in a `const` when the code
+/// is compiled, and a compile error is produced for IDs that are duplicates.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use id_newtype::IdEnum;
+///
+/// #[derive(Clone, Debug, PartialEq, Eq, IdEnum)]
+/// #[id_enum(K8sName<'s>)]
+/// pub enum Service<'s> {
+///     #[id_enum("web-server")]
+///     WebServer,
+///     Db,
+///     Other(K8sName<'s>),
+/// }
+/// ```
+#[proc_macro_derive(IdEnum, attributes(id_enum))]
+pub fn derive_id_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    id_enum_impl(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn id_enum_impl(input: &DeriveInput) -> syn::parse::Result<proc_macro2::TokenStream> {
+    let Data::Enum(data_enum) = &input.data else {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "`IdEnum` can only be derived for enums.",
+        ));
+    };
+    let Some(IdEnumAttrs { ty_path }) = IdEnumAttrs::from_attrs(&input.attrs)? else {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "Expected the ID type in an `#[id_enum(MyId)]` attribute.",
+        ));
+    };
+    let lifetime = generic_lifetime(input, "IdEnum")?;
+
+    // Generic arguments are not allowed in expressions, and are inferred there.
+    let mut ty_value_path = ty_path.clone();
+    if let Some(segment) = ty_value_path.segments.last_mut() {
+        segment.arguments = PathArguments::None;
+    }
+    let ty_name = ty_name(&ty_path);
+
+    let mut variant_names = Vec::new();
+    let mut proposed_ids = Vec::new();
+    let mut other_variant = None;
+    for variant in &data_enum.variants {
+        let id_enum_attr = variant
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("id_enum"));
+        match &variant.fields {
+            Fields::Unit => {
+                let proposed_id = match id_enum_attr {
+                    Some(attr) => attr.parse_args::<LitStr>()?,
+                    None => LitStr::new(
+                        &snake_case(&variant.ident.unraw().to_string()),
+                        variant.ident.span(),
+                    ),
+                };
+                variant_names.push(&variant.ident);
+                proposed_ids.push(proposed_id);
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 && other_variant.is_none() => {
+                if let Some(attr) = id_enum_attr {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        format!(
+                            "`#[id_enum(..)]` is not supported on the variant for other `{ty_name}`s."
+                        ),
+                    ));
+                }
+                other_variant = Some(&variant.ident);
+            }
+            Fields::Unnamed(_) | Fields::Named(_) => {
+                return Err(syn::Error::new(
+                    variant.span(),
+                    format!(
+                        "`IdEnum` variants must be unit variants, \
+                        or one variant for other `{ty_name}`s, e.g. `Other({ty_name})`."
+                    ),
+                ));
+            }
+        }
+    }
+
+    // The IDs are checked by the `const` item below, so the `From` impl
+    // constructs them unchecked.
+    let ids = unique_id_list(&ty_value_path, &proposed_ids);
+    let id_checks = proposed_ids
+        .iter()
+        .map(|proposed_id| const_id_check(proposed_id.span(), &ty_value_path, proposed_id));
+
+    let enum_name = &input.ident;
+    let variants_lifetime = lifetime.map_or_else(
+        || Lifetime::new("'static", Span::call_site()),
+        Lifetime::clone,
+    );
+    let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
+    let other_into_id =
+        other_variant.map(|other_variant| quote!(#enum_name::#other_variant(id) => id,));
+    let from_id = other_variant.map(|other_variant| {
+        quote! {
+            impl #impl_generics ::std::convert::From<#ty_path> for #enum_name #ty_generics {
+                fn from(id: #ty_path) -> Self {
+                    match id.as_str() {
+                        #(#proposed_ids => Self::#variant_names,)*
+                        _ => Self::#other_variant(id),
+                    }
+                }
+            }
+        }
+    });
+
+    // `'id_enum` is used for the `TryFrom` reference, as the enum's own lifetime
+    // may have any name.
+    let mut try_from_generics = input.generics.clone();
+    try_from_generics
+        .params
+        .insert(0, syn::parse_quote!('id_enum));
+    let (try_from_impl_generics, ..) = try_from_generics.split_for_impl();
+
+    Ok(quote! {
+        const _: () = {
+            #(#id_checks)*
+        };
+
+        impl #impl_generics ::std::convert::From<#enum_name #ty_generics> for #ty_path {
+            fn from(id_enum: #enum_name #ty_generics) -> Self {
+                match id_enum {
+                    #(#enum_name::#variant_names => #ids,)*
+                    #other_into_id
+                }
+            }
+        }
+
+        impl #try_from_impl_generics ::std::convert::TryFrom<&'id_enum #ty_path>
+            for #enum_name #ty_generics
+        {
+            type Error = &'id_enum #ty_path;
+
+            fn try_from(id: &'id_enum #ty_path) -> Result<Self, Self::Error> {
+                match id.as_str() {
+                    #(#proposed_ids => Ok(Self::#variant_names),)*
+                    _ => Err(id),
+                }
+            }
+        }
+
+        #from_id
+
+        impl #impl_generics #enum_name #ty_generics {
+            /// The enum variants for each well known ID.
+            pub const VARIANTS: &#variants_lifetime [Self] = &[#(Self::#variant_names),*];
+        }
+    })
+}
+
+/// Returns the name in `snake_case`, e.g. `web_server` for `WebServer`.
+fn snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut snake = String::with_capacity(name.len());
+    for (index, c) in chars.iter().copied().enumerate() {
+        if c.is_uppercase() && index > 0 {
+            let prev = chars[index - 1];
+            let next_is_lowercase = chars.get(index + 1).is_some_and(|next| next.is_lowercase());
+            // Acronyms are kept together, e.g. `HttpServer` and `HTTPServer` are
+            // both `http_server`.
+            if prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && next_is_lowercase)
+            {
+                snake.push('_');
+            }
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+/// Returns the lifetime parameter of the derive input, if any.
+///
+/// Type and const parameters, and more than one lifetime, are not supported.
+fn generic_lifetime<'input>(
+    input: &'input DeriveInput,
+    derive_name: &str,
+) -> syn::parse::Result<Option<&'input Lifetime>> {
+    let mut lifetimes = Vec::new();
+    for param in &input.generics.params {
+        match param {
+            GenericParam::Lifetime(lifetime_param) => lifetimes.push(&lifetime_param.lifetime),
+            GenericParam::Type(_) | GenericParam::Const(_) => {
+                return Err(syn::Error::new(
+                    param.span(),
+                    format!("`{derive_name}` does not support type or const parameters."),
+                ));
+            }
+        }
+    }
+
+    match lifetimes.as_slice() {
+        [] => Ok(None),
+        [lifetime] => Ok(Some(lifetime)),
+        [_, lifetime, ..] => Err(syn::Error::new(
+            lifetime.span(),
+            format!("`{derive_name}` supports at most one lifetime parameter."),
+        )),
+    }
+}
+
+/// Returns an error if the field type is not `Cow<'s, str>` for the struct's
+/// lifetime `'s`.
+fn ensure_cow_str(field_ty: &Type, lifetime: Option<&Lifetime>) -> syn::parse::Result<()> {
+    if is_cow_str(field_ty, lifetime) {
+        Ok(())
     } else {
-        let message = format!(
-            "`` is not a valid `{ty_name}`.\n\
-            `{ty_name}`s must begin with a letter or underscore, and contain only letters, numbers, or underscores."
+        let expected_lifetime = lifetime.map_or_else(
+            || Lifetime::new("'static", Span::call_site()),
+            Lifetime::clone,
         );
-        compile_fail(message)
+        Err(syn::Error::new(
+            field_ty.span(),
+            format!("Expected the field type to be `Cow<{expected_lifetime}, str>`."),
+        ))
     }
 }
 
-fn compile_fail(message: String) -> proc_macro2::TokenStream {
-    quote!(compile_error!(#message))
+/// Returns whether the field type is `Cow<'s, str>` for the given lifetime, or
+/// `Cow<'static, str>` if there is none.
+fn is_cow_str(field_ty: &Type, lifetime: Option<&Lifetime>) -> bool {
+    let expected_lifetime = lifetime.map_or_else(
+        || Lifetime::new("'static", Span::call_site()),
+        Lifetime::clone,
+    );
+    if let Type::Path(type_path) = field_ty
+        && type_path.qself.is_none()
+        && let Some(segment) = type_path.path.segments.last()
+        && segment.ident == "Cow"
+        && let PathArguments::AngleBracketed(args) = &segment.arguments
+        && let [GenericArgument::Lifetime(field_lifetime), GenericArgument::Type(Type::Path(str_path))] =
+            args.args.iter().collect::<Vec<_>>().as_slice()
+    {
+        field_lifetime.ident == expected_lifetime.ident && str_path.path.is_ident("str")
+    } else {
+        false
+    }
 }
 
-fn is_valid_id(proposed_id: &str) -> bool {
-    let mut chars = proposed_id.chars();
-    let first_char = chars.next();
-    let first_char_valid = first_char
-        .map(|c| c.is_ascii_alphabetic() || c == '_')
-        .unwrap_or(false);
-    let remainder_chars_valid =
-        chars.all(|c| c.is_ascii_alphabetic() || c == '_' || c.is_ascii_digit());
+/// Returns the construction of the ID, checked with the ID type's `validate`
+/// in a `const` block.
+///
+/// The check is evaluated by the compiler, so an invalid ID is a compile error
+/// at `span` with the same message as `new_const`, and the type's own rules
+/// (including its predicate) are always used. A missing ID is checked as an
+/// empty string.
+fn const_checked_id(
+    span: Span,
+    ty_path: &Path,
+    proposed_id: Option<&LitStr>,
+) -> proc_macro2::TokenStream {
+    let proposed_id = proposed_id
+        .cloned()
+        .unwrap_or_else(|| LitStr::new("", span));
+    let id_check = const_id_check(span, ty_path, &proposed_id);
+    quote_spanned! {span=>
+        {
+            const { #id_check }
+            <#ty_path>::new_unchecked(#proposed_id)
+        }
+    }
+}
+
+/// Returns a statement that panics with the first line of the error message
+/// if the ID is not valid for the ID type's `validate`.
+///
+/// This must be evaluated in a `const` context to be a compile error.
+fn const_id_check(span: Span, ty_path: &Path, proposed_id: &LitStr) -> proc_macro2::TokenStream {
+    let ty_name = ty_name(ty_path);
+    quote_spanned! {span=>
+        if let ::std::result::Result::Err(violation) = <#ty_path>::validate(#proposed_id) {
+            violation.panic(#ty_name, #proposed_id)
+        }
+    }
+}
+
+/// Returns the name of the ID type, which is the last segment of its path.
+fn ty_name(ty_path: &Path) -> String {
+    ty_path
+        .segments
+        .last()
+        .map(|segment| segment.ident.to_string())
+        .unwrap_or_default()
+}
 
-    first_char_valid && remainder_chars_valid
+fn compile_fail(span: Span, message: String) -> proc_macro2::TokenStream {
+    quote_spanned!(span=> compile_error!(#message))
 }
 
 #[cfg(test)]
 mod tests {
     use proc_macro2::Span;
-    use syn::LitStr;
+    use syn::{LitStr, Path};
 
-    use crate::LitStrMaybe;
+    use crate::{DeclareIdsArgs, IdPathArgs, IdsArgs};
 
-    use super::ensure_valid_id;
+    use super::{
+        checked_id_list, checked_id_path, const_checked_id, id_enum_impl, id_newtype_impl,
+        snake_case, unique_id_list,
+    };
+
+    fn ty_path() -> Path {
+        syn::parse_str("Ty").unwrap()
+    }
 
     #[test]
-    fn name_beginning_with_underscore_is_valid() {
-        let tokens = ensure_valid_id(
-            &LitStrMaybe(Some(LitStr::new("_", Span::call_site()))),
-            "Ty",
-            None,
-        );
+    fn checked_id_is_validated_in_const() {
+        let ty_path = syn::parse_str("crate::ids::Ty").unwrap();
+        let proposed_id = LitStr::new("Not_Valid", Span::call_site());
+        let tokens = const_checked_id(Span::call_site(), &ty_path, Some(&proposed_id));
 
-        assert_eq!(r#"Ty :: new_unchecked ("_")"#, tokens.to_string());
+        assert_eq!(
+            r#"{ const { if let :: std :: result :: Result :: Err (violation) = < crate :: ids :: Ty > :: validate ("Not_Valid") { violation . panic ("Ty" , "Not_Valid") } } < crate :: ids :: Ty > :: new_unchecked ("Not_Valid") }"#,
+            tokens.to_string()
+        );
     }
 
     #[test]
-    fn name_beginning_with_alpha_is_valid() {
-        let tokens = ensure_valid_id(
-            &LitStrMaybe(Some(LitStr::new("a", Span::call_site()))),
-            "Ty",
-            None,
-        );
-        assert_eq!(r#"Ty :: new_unchecked ("a")"#, tokens.to_string());
+    fn checked_id_none_is_validated_as_empty() {
+        let tokens = const_checked_id(Span::call_site(), &ty_path(), None);
 
-        let tokens = ensure_valid_id(
-            &LitStrMaybe(Some(LitStr::new("A", Span::call_site()))),
-            "Ty",
-            None,
+        assert_eq!(
+            r#"{ const { if let :: std :: result :: Result :: Err (violation) = < Ty > :: validate ("") { violation . panic ("Ty" , "") } } < Ty > :: new_unchecked ("") }"#,
+            tokens.to_string()
         );
-
-        assert_eq!(r#"Ty :: new_unchecked ("A")"#, tokens.to_string());
     }
 
     #[test]
-    fn name_beginning_with_number_is_invalid() {
-        let tokens = ensure_valid_id(
-            &LitStrMaybe(Some(LitStr::new("1", Span::call_site()))),
-            "Ty",
-            None,
-        );
+    fn derive_without_lifetime() {
+        let input = syn::parse_str(
+            r#"
+            #[id_newtype(macro = my_id, first = "a-z")]
+            struct MyId(Cow<'static, str>);
+            "#,
+        )
+        .unwrap();
+        let tokens = id_newtype_impl(&input).unwrap();
 
         assert_eq!(
-            "compile_error ! (\"\\\"1\\\" is not a valid `Ty`.\\n\
-            `Ty`s must begin with a letter or underscore, and contain only letters, numbers, or underscores.\")",
+            ":: id_newtype :: id_newtype ! (NEW ; MyId , MyIdInvalidFmt , [my_id]) ; \
+            :: id_newtype :: id_newtype ! (IMPL ; MyId , MyIdInvalidFmt , Cow < 'static , str > , [first = \"a-z\" ,]) ; \
+            :: id_newtype :: id_newtype ! (MACRO ; ($) MyId , my_id) ;",
             tokens.to_string()
         );
     }
 
     #[test]
-    fn name_containing_space_is_invalid() {
-        let tokens = ensure_valid_id(
-            &LitStrMaybe(Some(LitStr::new("a b", Span::call_site()))),
-            "Ty",
-            None,
-        );
+    fn derive_with_lifetime() {
+        let input = syn::parse_str(
+            r#"
+            #[id_newtype(error = MyIdError)]
+            struct MyId<'s>(std::borrow::Cow<'s, str>);
+            "#,
+        )
+        .unwrap();
+        let tokens = id_newtype_impl(&input).unwrap();
 
         assert_eq!(
-            "compile_error ! (\"\\\"a b\\\" is not a valid `Ty`.\\n\
-            `Ty`s must begin with a letter or underscore, and contain only letters, numbers, or underscores.\")",
+            ":: id_newtype :: id_newtype ! (NEW_LT ; MyId , MyIdError , 's , []) ; \
+            :: id_newtype :: id_newtype ! (IMPL_LT ; MyId , MyIdError , 's , []) ;",
             tokens.to_string()
         );
     }
 
     #[test]
-    fn name_containing_hyphen_is_invalid() {
-        let tokens = ensure_valid_id(
-            &LitStrMaybe(Some(LitStr::new("a-b", Span::call_site()))),
-            "Ty",
-            None,
-        );
+    fn derive_with_storage() {
+        let input = syn::parse_str("struct MyId(std::sync::Arc<str>);").unwrap();
+        let tokens = id_newtype_impl(&input).unwrap();
 
         assert_eq!(
-            "compile_error ! (\"\\\"a-b\\\" is not a valid `Ty`.\\n\
-            `Ty`s must begin with a letter or underscore, and contain only letters, numbers, or underscores.\")",
+            ":: id_newtype :: id_newtype ! \
+            (NEW_STORAGE ; MyId , MyIdInvalidFmt , std :: sync :: Arc < str > , []) ; \
+            :: id_newtype :: id_newtype ! \
+            (IMPL ; MyId , MyIdInvalidFmt , std :: sync :: Arc < str > , []) ;",
             tokens.to_string()
         );
     }
 
     #[test]
-    fn name_empty_string_is_invalid() {
-        let tokens = ensure_valid_id(
-            &LitStrMaybe(Some(LitStr::new("", Span::call_site()))),
-            "Ty",
-            None,
+    fn derive_unsupported_shapes_are_errors() {
+        [
+            (
+                "struct MyId { id: Cow<'static, str> }",
+                "`IdNewtype` can only be derived for tuple structs with a single field, \
+                e.g. `struct MyId(Cow<'static, str>);`.",
+            ),
+            (
+                "enum MyId { A }",
+                "`IdNewtype` can only be derived for tuple structs with a single field, \
+                e.g. `struct MyId(Cow<'static, str>);`.",
+            ),
+            (
+                "struct MyId<T>(Cow<'static, str>, T);",
+                "`IdNewtype` does not support type or const parameters.",
+            ),
+            (
+                "struct MyId<'a, 'b>(Cow<'a, str>);",
+                "`IdNewtype` supports at most one lifetime parameter.",
+            ),
+            (
+                "struct MyId<'s>(Cow<'static, str>);",
+                "Expected the field type to be `Cow<'s, str>`.",
+            ),
+            (
+                "#[id_newtype(frist = \"a-z\")] struct MyId(Cow<'static, str>);",
+                "Unknown `id_newtype` option: `frist`.",
+            ),
+            (
+                "#[id_newtype(error = A)] #[id_newtype(error = B)] struct MyId(Cow<'static, str>);",
+                "Duplicate `error` option.",
+            ),
+        ]
+        .into_iter()
+        .for_each(|(input, expected)| {
+            let input = syn::parse_str(input).unwrap();
+            let error =
+                id_newtype_impl(&input).expect_err("Expected unsupported shape to be an error.");
+
+            assert_eq!(expected, error.to_string());
+        });
+    }
+
+    #[test]
+    fn derive_id_enum_unsupported_shapes_are_errors() {
+        [
+            (
+                "#[id_enum(MyId)] struct Service;",
+                "`IdEnum` can only be derived for enums.",
+            ),
+            (
+                "enum Service { Web }",
+                "Expected the ID type in an `#[id_enum(MyId)]` attribute.",
+            ),
+            (
+                "#[id_enum(MyId)] #[id_enum(MyId)] enum Service { Web }",
+                "Duplicate `#[id_enum(..)]` attribute.",
+            ),
+            (
+                "#[id_enum(MyId)] enum Service { Web { port: u16 } }",
+                "`IdEnum` variants must be unit variants, \
+                or one variant for other `MyId`s, e.g. `Other(MyId)`.",
+            ),
+            (
+                "#[id_enum(MyId)] enum Service { Other(MyId), Unknown(MyId) }",
+                "`IdEnum` variants must be unit variants, \
+                or one variant for other `MyId`s, e.g. `Other(MyId)`.",
+            ),
+            (
+                "#[id_enum(MyId)] enum Service<T> { Web, Other(T) }",
+                "`IdEnum` does not support type or const parameters.",
+            ),
+        ]
+        .into_iter()
+        .for_each(|(input, expected)| {
+            let input = syn::parse_str(input).unwrap();
+            let error =
+                id_enum_impl(&input).expect_err("Expected unsupported shape to be an error.");
+
+            assert_eq!(expected, error.to_string());
+        });
+    }
+
+    #[test]
+    fn derive_id_enum_with_duplicate_is_error() {
+        let input = syn::parse_str(
+            r#"
+            #[id_enum(MyId)]
+            enum Service {
+                #[id_enum("db")]
+                Database,
+                Db,
+            }
+            "#,
+        )
+        .unwrap();
+        let tokens = id_enum_impl(&input).unwrap().to_string();
+
+        assert!(tokens.contains(
+            r#"compile_error ! ("`db` is a duplicate `MyId`, it is already in this list.")"#
+        ));
+    }
+
+    #[test]
+    fn derive_id_enum_checks_ids_in_const() {
+        let input = syn::parse_str(
+            r#"
+            #[id_enum(MyId<'s>)]
+            enum Service<'s> {
+                Db,
+                Other(MyId<'s>),
+            }
+            "#,
+        )
+        .unwrap();
+        let tokens = id_enum_impl(&input).unwrap().to_string();
+
+        assert!(tokens.starts_with(
+            r#"const _ : () = { if let :: std :: result :: Result :: Err (violation) = < MyId > :: validate ("db") { violation . panic ("MyId" , "db") } } ;"#
+        ));
+    }
+
+    #[test]
+    fn snake_case_splits_words() {
+        assert_eq!("web", snake_case("Web"));
+        assert_eq!("web_server", snake_case("WebServer"));
+        assert_eq!("http_server", snake_case("HttpServer"));
+        assert_eq!("http_server", snake_case("HTTPServer"));
+        assert_eq!("web2_server", snake_case("Web2Server"));
+    }
+
+    #[test]
+    fn id_list_with_duplicate_is_error_at_duplicate() {
+        let proposed_ids =
+            ["web", "db", "web"].map(|proposed_id| LitStr::new(proposed_id, Span::call_site()));
+        let tokens = unique_id_list(&ty_path(), &proposed_ids);
+
+        assert_eq!(
+            vec![
+                r#"Ty :: new_unchecked ("web")"#.to_string(),
+                r#"Ty :: new_unchecked ("db")"#.to_string(),
+                r#"compile_error ! ("`web` is a duplicate `Ty`, it is already in this list.")"#
+                    .to_string(),
+            ],
+            tokens
+                .iter()
+                .map(|tokens| tokens.to_string())
+                .collect::<Vec<_>>()
         );
+    }
+
+    #[test]
+    fn id_list_is_validated_in_const() {
+        let proposed_ids =
+            ["web", "a b"].map(|proposed_id| LitStr::new(proposed_id, Span::call_site()));
+        let tokens = checked_id_list(&ty_path(), &proposed_ids);
 
         assert_eq!(
-            "compile_error ! (\"\\\"\\\" is not a valid `Ty`.\\n\
-            `Ty`s must begin with a letter or underscore, and contain only letters, numbers, or underscores.\")",
-            tokens.to_string()
+            r#"{ const { if let :: std :: result :: Result :: Err (violation) = < Ty > :: validate ("a b") { violation . panic ("Ty" , "a b") } } < Ty > :: new_unchecked ("a b") }"#,
+            tokens[1].to_string()
         );
     }
 
     #[test]
-    fn name_none_is_invalid() {
-        let tokens = ensure_valid_id(&LitStrMaybe(None), "Ty", None);
+    fn ids_args_parse() {
+        let ids_args: IdsArgs = syn::parse_str(r#"crate::ids::Ty; "web", "db""#).unwrap();
+
+        assert_eq!(2, ids_args.proposed_ids.len());
+        assert!(syn::parse_str::<IdsArgs>(r#"Ty; "web"; first = "a-z""#).is_err());
+    }
+
+    #[test]
+    fn id_path_segments_are_validated_in_const() {
+        let id_path_args: IdPathArgs = syn::parse_str(r#"Ty, "us_east.node_1""#).unwrap();
+        let tokens = checked_id_path(&id_path_args).to_string();
+
+        assert!(tokens.ends_with(
+            r#":: id_newtype :: IdPath :: < Ty , '.' > :: new_unchecked (:: std :: vec ! [{ const { if let :: std :: result :: Result :: Err (violation) = < Ty > :: validate ("us_east") { violation . panic ("Ty" , "us_east") } } < Ty > :: new_unchecked ("us_east") } , { const { if let :: std :: result :: Result :: Err (violation) = < Ty > :: validate ("node_1") { violation . panic ("Ty" , "node_1") } } < Ty > :: new_unchecked ("node_1") }]) }"#
+        ));
+    }
+
+    #[test]
+    fn id_path_separator_is_checked_in_const() {
+        let id_path_args: IdPathArgs = syn::parse_str(r#"Ty, "us-east"; separator = '-'"#).unwrap();
+        let tokens = checked_id_path(&id_path_args).to_string();
+
+        assert!(tokens.starts_with(
+            r#"{ const { assert ! (! < Ty > :: RULES . first_class () . contains ('-') && ! < Ty > :: RULES . rest_class () . contains ('-') , "`-` cannot separate `Ty`s, as it is allowed in them.") ; }"#
+        ));
+    }
+
+    #[test]
+    fn id_path_unknown_option_is_error() {
+        let error = syn::parse_str::<IdPathArgs>(r#"Ty, "us-east"; first = "a-z""#)
+            .err()
+            .expect("Expected unknown option to be an error.");
+
+        assert_eq!("Unknown `id_path` option: `first`.", error.to_string());
+    }
+
+    #[test]
+    fn declare_ids_args_parse() {
+        let declare_ids_args: DeclareIdsArgs = syn::parse_str(
+            r#"
+            Ty;
+
+            /// The web server.
+            pub const WEB = "web";
+            const DB = "db";
+            pub(crate) const ALL_SERVICES;
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(2, declare_ids_args.id_consts.len());
+        assert_eq!("WEB", declare_ids_args.id_consts[0].name.to_string());
+        assert_eq!(1, declare_ids_args.id_consts[0].attrs.len());
+        let all_const = declare_ids_args.all_const.unwrap();
+        assert_eq!("ALL_SERVICES", all_const.name.to_string());
+        assert!(matches!(all_const.vis, syn::Visibility::Restricted(_)));
+
+        let declare_ids_args: DeclareIdsArgs = syn::parse_str(r#"Ty; const WEB = "web";"#).unwrap();
+        assert!(declare_ids_args.all_const.is_none());
+    }
+
+    #[test]
+    fn declare_ids_with_duplicate_all_const_is_error() {
+        let error = syn::parse_str::<DeclareIdsArgs>(r#"Ty; const WEB = "web"; const A; const B;"#)
+            .err()
+            .expect("Expected a second constant for all IDs to be an error.");
 
         assert_eq!(
-            "compile_error ! (\"`` is not a valid `Ty`.\\n\
-            `Ty`s must begin with a letter or underscore, and contain only letters, numbers, or underscores.\")",
-            tokens.to_string()
+            "Duplicate constant for all IDs, only one `const NAME;` may be declared.",
+            error.to_string()
         );
     }
 }
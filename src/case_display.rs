@@ -0,0 +1,28 @@
+use std::fmt;
+
+use crate::CaseStyle;
+
+/// Displays an ID in a [`CaseStyle`], returned by
+/// [`IdNewtype::display_case`].
+///
+/// [`IdNewtype::display_case`]: crate::IdNewtype::display_case
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CaseDisplay<'s> {
+    /// The ID to display.
+    id: &'s str,
+    /// Case style to display the ID in.
+    style: CaseStyle,
+}
+
+impl<'s> CaseDisplay<'s> {
+    /// Returns a new `CaseDisplay`.
+    pub fn new(id: &'s str, style: CaseStyle) -> Self {
+        Self { id, style }
+    }
+}
+
+impl fmt::Display for CaseDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.style.write(self.id, f)
+    }
+}
@@ -0,0 +1,208 @@
+use std::fmt::{self, Write};
+
+use crate::{InvalidReason, Words};
+
+/// Case style that an ID may be rendered in.
+///
+/// Leading underscores are kept, and other characters between [`Words`] are
+/// replaced by the style's separator, if any:
+///
+/// ```rust
+/// use id_newtype::CaseStyle;
+///
+/// assert_eq!("web_server", CaseStyle::Snake.convert("webServer"));
+/// assert_eq!("webServer", CaseStyle::Camel.convert("web_server"));
+/// assert_eq!("HttpServer", CaseStyle::Pascal.convert("HTTP_SERVER"));
+/// assert_eq!(
+///     "_WEB_SERVER",
+///     CaseStyle::ScreamingSnake.convert("_WebServer")
+/// );
+/// ```
+///
+/// The result always matches `[A-Za-z_][A-Za-z0-9_]*`: `_` is added if it
+/// would otherwise begin with a digit or be empty.
+///
+/// ID types may also enforce a case style with the `style = Snake` option of
+/// `id_newtype!`. See [`IdRules::style`] for the rules of each style.
+///
+/// [`IdRules::style`]: crate::IdRules::style
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CaseStyle {
+    /// `snake_case`.
+    Snake,
+    /// `camelCase`.
+    Camel,
+    /// `PascalCase`.
+    Pascal,
+    /// `SCREAMING_SNAKE_CASE`.
+    ScreamingSnake,
+}
+
+impl CaseStyle {
+    /// Returns the name of the case style, written in that style, e.g.
+    /// `"camelCase"`.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Snake => "snake_case",
+            Self::Camel => "camelCase",
+            Self::Pascal => "PascalCase",
+            Self::ScreamingSnake => "SCREAMING_SNAKE_CASE",
+        }
+    }
+
+    /// Returns the characters that an ID in this style may begin with.
+    pub(crate) const fn first_spec(self) -> &'static str {
+        match self {
+            Self::Snake | Self::Camel => "a-z",
+            Self::Pascal | Self::ScreamingSnake => "A-Z",
+        }
+    }
+
+    /// Returns the characters that an ID in this style may contain after the
+    /// first character.
+    pub(crate) const fn rest_spec(self) -> &'static str {
+        match self {
+            Self::Snake => "a-z0-9_",
+            Self::Camel | Self::Pascal => "A-Za-z0-9",
+            Self::ScreamingSnake => "A-Z0-9_",
+        }
+    }
+
+    /// Returns whether IDs in this style must not contain repeated or trailing
+    /// underscores.
+    pub(crate) const fn has_underscore_rules(self) -> bool {
+        matches!(self, Self::Snake | Self::ScreamingSnake)
+    }
+
+    /// Returns the reason that the byte at `offset` breaks this style's case
+    /// or underscore rules, if any.
+    ///
+    /// Other characters are left to the character classes.
+    pub(crate) const fn violation(self, bytes: &[u8], offset: usize) -> Option<InvalidReason> {
+        let b = bytes[offset];
+        if b.is_ascii_uppercase()
+            && (matches!(self, Self::Snake) || (offset == 0 && matches!(self, Self::Camel)))
+        {
+            Some(InvalidReason::UppercaseLetter)
+        } else if b.is_ascii_lowercase()
+            && (matches!(self, Self::ScreamingSnake)
+                || (offset == 0 && matches!(self, Self::Pascal)))
+        {
+            Some(InvalidReason::LowercaseLetter)
+        } else if self.has_underscore_rules() && b == b'_' {
+            if offset > 0 && bytes[offset - 1] == b'_' {
+                Some(InvalidReason::RepeatedUnderscore)
+            } else if offset + 1 == bytes.len() {
+                Some(InvalidReason::TrailingUnderscore)
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Returns the given `&str` in this case style.
+    pub fn convert(self, s: &str) -> String {
+        let mut converted = String::with_capacity(s.len());
+        self.write(s, &mut converted)
+            .expect("Writing to a `String` does not fail.");
+        converted
+    }
+
+    /// Writes the given `&str` in this case style.
+    pub(crate) fn write<W>(self, s: &str, out: &mut W) -> fmt::Result
+    where
+        W: Write,
+    {
+        let underscores = &s[..s.len() - s.trim_start_matches('_').len()];
+        out.write_str(underscores)?;
+
+        let mut is_empty = underscores.is_empty();
+        for (index, word) in Words::new(s).enumerate() {
+            if index == 0 {
+                if is_empty && word.starts_with(|c: char| c.is_ascii_digit()) {
+                    out.write_char('_')?;
+                }
+            } else if let Self::Snake | Self::ScreamingSnake = self {
+                out.write_char('_')?;
+            }
+
+            let (first, rest) = word.split_at(1);
+            let (first, rest) = match self {
+                Self::Snake => (first.to_ascii_lowercase(), rest.to_ascii_lowercase()),
+                Self::Camel if index == 0 => {
+                    (first.to_ascii_lowercase(), rest.to_ascii_lowercase())
+                }
+                Self::Camel | Self::Pascal => {
+                    (first.to_ascii_uppercase(), rest.to_ascii_lowercase())
+                }
+                Self::ScreamingSnake => (first.to_ascii_uppercase(), rest.to_ascii_uppercase()),
+            };
+            out.write_str(&first)?;
+            out.write_str(&rest)?;
+            is_empty = false;
+        }
+
+        if is_empty {
+            out.write_char('_')?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for CaseStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::InvalidReason;
+
+    use super::CaseStyle;
+
+    #[test]
+    fn convert() {
+        assert_eq!("webServer", CaseStyle::Camel.convert("WebServer"));
+        assert_eq!("WebServer", CaseStyle::Pascal.convert("web-server"));
+        assert_eq!("__web_server", CaseStyle::Snake.convert("__WebServer"));
+        assert_eq!("_2_fa_code", CaseStyle::Snake.convert("2FACode"));
+        assert_eq!("_", CaseStyle::ScreamingSnake.convert("--"));
+    }
+
+    #[test]
+    fn violation() {
+        let violation = |style: CaseStyle, s: &str| {
+            (0..s.len()).find_map(|offset| {
+                style
+                    .violation(s.as_bytes(), offset)
+                    .map(|reason| (reason, offset))
+            })
+        };
+
+        assert_eq!(None, violation(CaseStyle::Snake, "web_server_2"));
+        assert_eq!(
+            Some((InvalidReason::UppercaseLetter, 0)),
+            violation(CaseStyle::Snake, "WeB__x_")
+        );
+        assert_eq!(
+            Some((InvalidReason::RepeatedUnderscore, 4)),
+            violation(CaseStyle::Snake, "web__x_")
+        );
+        assert_eq!(
+            Some((InvalidReason::TrailingUnderscore, 5)),
+            violation(CaseStyle::ScreamingSnake, "WEB_X_")
+        );
+        assert_eq!(
+            Some((InvalidReason::LowercaseLetter, 1)),
+            violation(CaseStyle::ScreamingSnake, "Web")
+        );
+        assert_eq!(None, violation(CaseStyle::Camel, "webServer"));
+        assert_eq!(
+            Some((InvalidReason::LowercaseLetter, 0)),
+            violation(CaseStyle::Pascal, "webServer")
+        );
+    }
+}
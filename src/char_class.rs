@@ -0,0 +1,363 @@
+use std::fmt;
+
+use crate::const_str;
+
+/// Set of ASCII characters, parsed from a regex-like class specification.
+///
+/// The specification is the content of a regex character class without the
+/// surrounding brackets, e.g. `"A-Za-z0-9_"`. A `-` is a literal hyphen when it
+/// is the first or last character.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CharClass {
+    /// The specification this class was parsed from.
+    spec: &'static str,
+    /// Bit `n` is set when ASCII character `n` is in the class.
+    bits: u128,
+}
+
+impl CharClass {
+    /// Returns a new `CharClass` parsed from the given specification.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the specification contains non-ASCII characters, or a range
+    /// whose start is after its end. When used in a `const`, this is a compile
+    /// error.
+    pub const fn new(spec: &'static str) -> Self {
+        let bytes = spec.as_bytes();
+        let mut bits = 0u128;
+        let mut i = 0;
+        while i < bytes.len() {
+            let start = bytes[i];
+            assert!(
+                start.is_ascii(),
+                "Character classes may only contain ASCII characters."
+            );
+
+            if i + 2 < bytes.len() && bytes[i + 1] == b'-' {
+                let end = bytes[i + 2];
+                assert!(
+                    end.is_ascii(),
+                    "Character classes may only contain ASCII characters."
+                );
+                assert!(
+                    start <= end,
+                    "Character class range start must not be after its end."
+                );
+
+                let mut c = start;
+                while c <= end {
+                    bits |= 1 << c;
+                    c += 1;
+                }
+                i += 3;
+            } else {
+                bits |= 1 << start;
+                i += 1;
+            }
+        }
+
+        Self { spec, bits }
+    }
+
+    /// Returns the specification this class was parsed from.
+    pub const fn spec(&self) -> &'static str {
+        self.spec
+    }
+
+    /// Returns whether the given character is in this class.
+    pub const fn contains(&self, c: char) -> bool {
+        c.is_ascii() && self.bits & (1 << c as u32) != 0
+    }
+
+    /// Returns whether this class contains no characters.
+    pub const fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Returns an iterator over the characters in this class, in ascending
+    /// order.
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        (0u8..128)
+            .filter(|c| self.bits & (1 << c) != 0)
+            .map(char::from)
+    }
+
+    /// Returns a `Display` adapter describing a single character of this
+    /// class, e.g. "a letter or underscore".
+    pub fn describe_one(&self) -> impl fmt::Display + '_ {
+        CharClassDescription {
+            char_class: self,
+            plural: false,
+        }
+    }
+
+    /// Returns a `Display` adapter describing any characters of this class,
+    /// e.g. "letters, numbers, or underscores".
+    pub fn describe_any(&self) -> impl fmt::Display + '_ {
+        CharClassDescription {
+            char_class: self,
+            plural: true,
+        }
+    }
+
+    /// Writes this class as a regex character class, e.g. `[a-z0-9\-]`, into
+    /// `buf` at `len`, and returns the new length.
+    ///
+    /// Letters and numbers are written first, then other characters in ASCII
+    /// order. Characters that are special in a class are escaped, so the class
+    /// is valid in both ECMAScript and Rust regexes.
+    pub(crate) const fn write_pattern(&self, buf: &mut [u8], len: usize) -> usize {
+        const GROUPS: [u128; 3] = [
+            range_bits(b'A', b'Z'),
+            range_bits(b'a', b'z'),
+            range_bits(b'0', b'9'),
+        ];
+
+        let mut len = const_str::write(buf, len, b"[");
+        let mut remaining = self.bits;
+        let mut i = 0;
+        while i < GROUPS.len() {
+            len = write_runs(buf, len, remaining & GROUPS[i]);
+            remaining &= !GROUPS[i];
+            i += 1;
+        }
+        len = write_runs(buf, len, remaining);
+        const_str::write(buf, len, b"]")
+    }
+
+    /// Returns the names of the groups of characters in this class.
+    fn group_names(&self, plural: bool) -> Vec<String> {
+        const LOWER: u128 = range_bits(b'a', b'z');
+        const UPPER: u128 = range_bits(b'A', b'Z');
+        const DIGIT: u128 = range_bits(b'0', b'9');
+
+        let suffix = if plural { "s" } else { "" };
+        let mut names = Vec::new();
+        let mut remaining = self.bits;
+
+        if remaining & (LOWER | UPPER) == LOWER | UPPER {
+            names.push(format!("letter{suffix}"));
+            remaining &= !(LOWER | UPPER);
+        } else if remaining & LOWER == LOWER {
+            names.push(format!("lowercase letter{suffix}"));
+            remaining &= !LOWER;
+        } else if remaining & UPPER == UPPER {
+            names.push(format!("uppercase letter{suffix}"));
+            remaining &= !UPPER;
+        }
+        if remaining & DIGIT == DIGIT {
+            names.push(format!("number{suffix}"));
+            remaining &= !DIGIT;
+        }
+
+        let mut c = 0u8;
+        while c < 128 {
+            if remaining & (1 << c) == 0 {
+                c += 1;
+                continue;
+            }
+            let start = c;
+            while c < 128 && remaining & (1 << c) != 0 {
+                c += 1;
+            }
+            let end = c - 1;
+
+            if start == end {
+                let name = match start {
+                    b'_' => format!("underscore{suffix}"),
+                    b'-' => format!("hyphen{suffix}"),
+                    b'.' => format!("period{suffix}"),
+                    _ => format!("`{}`", start as char),
+                };
+                names.push(name);
+            } else {
+                names.push(format!("`{}`-`{}`", start as char, end as char));
+            }
+        }
+
+        names
+    }
+}
+
+/// Returns the bits for the given inclusive range of ASCII characters.
+const fn range_bits(start: u8, end: u8) -> u128 {
+    let mut bits = 0u128;
+    let mut c = start;
+    while c <= end {
+        bits |= 1 << c;
+        c += 1;
+    }
+    bits
+}
+
+/// Writes the characters in `bits` into `buf` at `len`, with runs of three or
+/// more characters written as ranges, and returns the new length.
+const fn write_runs(buf: &mut [u8], mut len: usize, bits: u128) -> usize {
+    let mut c = 0u8;
+    while c < 128 {
+        if bits & (1 << c) == 0 {
+            c += 1;
+            continue;
+        }
+        let start = c;
+        while c < 128 && bits & (1 << c) != 0 {
+            c += 1;
+        }
+        let end = c - 1;
+
+        if end - start >= 2 {
+            len = write_class_char(buf, len, start);
+            len = const_str::write(buf, len, b"-");
+            len = write_class_char(buf, len, end);
+        } else {
+            let mut c = start;
+            while c <= end {
+                len = write_class_char(buf, len, c);
+                c += 1;
+            }
+        }
+    }
+    len
+}
+
+/// Writes an ASCII character inside a regex character class, escaping it if
+/// necessary, and returns the new length.
+const fn write_class_char(buf: &mut [u8], len: usize, c: u8) -> usize {
+    const HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+    match c {
+        b'\\' | b']' | b'[' | b'^' | b'-' => const_str::write(buf, len, &[b'\\', c]),
+        0x00..=0x1F | 0x7F => const_str::write(
+            buf,
+            len,
+            &[
+                b'\\',
+                b'x',
+                HEX_DIGITS[(c >> 4) as usize],
+                HEX_DIGITS[(c & 0xF) as usize],
+            ],
+        ),
+        _ => const_str::write(buf, len, &[c]),
+    }
+}
+
+/// Describes the characters in a [`CharClass`].
+struct CharClassDescription<'c> {
+    char_class: &'c CharClass,
+    plural: bool,
+}
+
+impl fmt::Display for CharClassDescription<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names = self.char_class.group_names(self.plural);
+        if !self.plural
+            && let Some(first) = names.first()
+            && !first.starts_with('`')
+        {
+            let article = if first.starts_with(['a', 'e', 'i', 'o', 'u']) {
+                "an"
+            } else {
+                "a"
+            };
+            write!(f, "{article} ")?;
+        }
+
+        match names.as_slice() {
+            [] => write!(f, "nothing"),
+            [name] => write!(f, "{name}"),
+            [name_0, name_1] => write!(f, "{name_0} or {name_1}"),
+            [names @ .., name_last] => {
+                names.iter().try_for_each(|name| write!(f, "{name}, "))?;
+                write!(f, "or {name_last}")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CharClass;
+
+    #[test]
+    fn contains() {
+        let char_class = CharClass::new("a-z0-9-");
+
+        assert!(char_class.contains('a'));
+        assert!(char_class.contains('z'));
+        assert!(char_class.contains('5'));
+        assert!(char_class.contains('-'));
+        assert!(!char_class.contains('A'));
+        assert!(!char_class.contains('_'));
+        assert!(!char_class.contains('é'));
+    }
+
+    #[test]
+    fn hyphen_at_start_is_literal() {
+        let char_class = CharClass::new("-a");
+
+        assert!(char_class.contains('-'));
+        assert!(char_class.contains('a'));
+        assert!(!char_class.contains('b'));
+    }
+
+    #[test]
+    fn chars() {
+        let char_class = CharClass::new("a-c_0");
+
+        assert_eq!(
+            vec!['0', '_', 'a', 'b', 'c'],
+            char_class.chars().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn describe_default() {
+        assert_eq!(
+            "a letter or underscore",
+            CharClass::new("A-Za-z_").describe_one().to_string()
+        );
+        assert_eq!(
+            "letters, numbers, or underscores",
+            CharClass::new("A-Za-z0-9_").describe_any().to_string()
+        );
+    }
+
+    #[test]
+    fn describe_custom() {
+        assert_eq!(
+            "a lowercase letter",
+            CharClass::new("a-z").describe_one().to_string()
+        );
+        assert_eq!(
+            "lowercase letters, numbers, or hyphens",
+            CharClass::new("a-z0-9-").describe_any().to_string()
+        );
+        assert_eq!(
+            "an uppercase letter or `$`",
+            CharClass::new("A-Z$").describe_one().to_string()
+        );
+        assert_eq!(
+            "numbers or `a`-`f`",
+            CharClass::new("a-f0-9").describe_any().to_string()
+        );
+    }
+
+    #[test]
+    fn write_pattern() {
+        let pattern = |spec| {
+            let char_class = CharClass::new(spec);
+            let mut buf = [0u8; 64];
+            let len = char_class.write_pattern(&mut buf, 0);
+            String::from_utf8(buf[..len].to_vec()).unwrap()
+        };
+
+        assert_eq!("[A-Za-z_]", pattern("A-Za-z_"));
+        assert_eq!("[A-Za-z0-9_]", pattern("_0-9a-zA-Z"));
+        assert_eq!("[a-z0-9\\-]", pattern("a-z0-9-"));
+        assert_eq!("[ab.]", pattern("a-b."));
+        assert_eq!("[\\x00 \\[\\^]", pattern("[^\0 "));
+        assert_eq!("[\\\\-\\^]", pattern("\\]^"));
+        assert_eq!("[]", pattern(""));
+    }
+}
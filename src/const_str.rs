@@ -0,0 +1,179 @@
+//! `const fn` equivalents of `str` methods that are not `const`, and writers
+//! for building strings in `const` contexts.
+
+/// Returns whether the two `&str`s are equal.
+pub(crate) const fn eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Returns whether the two `&str`s are equal, ignoring ASCII case.
+pub(crate) const fn eq_ignore_ascii_case(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < a.len() {
+        if !a[i].eq_ignore_ascii_case(&b[i]) {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Returns whether `s` begins with `prefix`.
+pub(crate) const fn starts_with(s: &str, prefix: &str) -> bool {
+    s.len() >= prefix.len() && eq(s.split_at(prefix.len()).0, prefix)
+}
+
+/// Returns the byte offset of the first occurrence of `pattern` in `s`, if
+/// any.
+pub(crate) const fn find(s: &str, pattern: &str) -> Option<usize> {
+    let (s, pattern) = (s.as_bytes(), pattern.as_bytes());
+    let mut offset = 0;
+    while offset + pattern.len() <= s.len() {
+        let mut i = 0;
+        while i < pattern.len() && s[offset + i] == pattern[i] {
+            i += 1;
+        }
+        if i == pattern.len() {
+            return Some(offset);
+        }
+        offset += 1;
+    }
+    None
+}
+
+/// Returns the `char` at the given byte offset, which must be a character
+/// boundary.
+pub(crate) const fn char_at(s: &str, offset: usize) -> char {
+    let bytes = s.as_bytes();
+    let first = bytes[offset] as u32;
+    let (len, mut code_point) = if first < 0x80 {
+        (1, first)
+    } else if first < 0xE0 {
+        (2, first & 0x1F)
+    } else if first < 0xF0 {
+        (3, first & 0x0F)
+    } else {
+        (4, first & 0x07)
+    };
+
+    let mut i = 1;
+    while i < len {
+        code_point = (code_point << 6) | (bytes[offset + i] as u32 & 0x3F);
+        i += 1;
+    }
+
+    match char::from_u32(code_point) {
+        Some(c) => c,
+        None => char::REPLACEMENT_CHARACTER,
+    }
+}
+
+/// Returns the number of `char`s before the given byte offset.
+pub(crate) const fn char_count(s: &str, offset: usize) -> usize {
+    let bytes = s.as_bytes();
+    let mut count = 0;
+    let mut i = 0;
+    while i < offset && i < bytes.len() {
+        // Continuation bytes are `0b10xx_xxxx`.
+        if bytes[i] & 0xC0 != 0x80 {
+            count += 1;
+        }
+        i += 1;
+    }
+    count
+}
+
+/// Writes `bytes` into `buf` at `len`, and returns the new length.
+///
+/// Bytes past the end of `buf` are not written, so passing an empty `buf`
+/// returns the length that would be written.
+pub(crate) const fn write(buf: &mut [u8], mut len: usize, bytes: &[u8]) -> usize {
+    let mut i = 0;
+    while i < bytes.len() {
+        if len < buf.len() {
+            buf[len] = bytes[i];
+        }
+        len += 1;
+        i += 1;
+    }
+    len
+}
+
+/// Writes the decimal digits of `n` into `buf` at `len`, and returns the new
+/// length.
+pub(crate) const fn write_usize(buf: &mut [u8], len: usize, n: usize) -> usize {
+    let mut digits = [0u8; 20];
+    let mut start = digits.len();
+    let mut n = n;
+    loop {
+        start -= 1;
+        digits[start] = b'0' + (n % 10) as u8;
+        n /= 10;
+        if n == 0 {
+            break;
+        }
+    }
+    write(buf, len, digits.split_at(start).1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        char_at, char_count, eq, eq_ignore_ascii_case, find, starts_with, write, write_usize,
+    };
+
+    #[test]
+    fn comparisons() {
+        assert!(eq("abc", "abc"));
+        assert!(!eq("abc", "abd"));
+        assert!(!eq("abc", "ab"));
+        assert!(eq_ignore_ascii_case("Select", "SELECT"));
+        assert!(!eq_ignore_ascii_case("select", "selects"));
+        assert!(starts_with("__init", "__"));
+        assert!(!starts_with("_", "__"));
+        assert_eq!(Some(5), find("a_b_c::d::e", "::"));
+        assert_eq!(Some(2), find("é::", "::"));
+        assert_eq!(None, find("a:b", "::"));
+    }
+
+    #[test]
+    fn chars() {
+        let s = "aé日😀";
+
+        assert_eq!('a', char_at(s, 0));
+        assert_eq!('é', char_at(s, 1));
+        assert_eq!('日', char_at(s, 3));
+        assert_eq!('😀', char_at(s, 6));
+        assert_eq!(3, char_count(s, 6));
+    }
+
+    #[test]
+    fn writes() {
+        let mut buf = [0u8; 8];
+        let len = write(&mut buf, 0, b"a{");
+        let len = write_usize(&mut buf, len, 0);
+        let len = write(&mut buf, len, b",");
+        let len = write_usize(&mut buf, len, 63);
+
+        assert_eq!(6, len);
+        assert_eq!(b"a{0,63", buf.split_at(len).0);
+        assert_eq!(9, write(&mut [], 0, b"truncated"));
+    }
+}
@@ -0,0 +1,40 @@
+/// Delimiter between the namespace and local parts of a [`Qualified`] ID.
+///
+/// [`DoubleColon`] is the default. Other delimiters are declared as marker
+/// types:
+///
+/// ```rust
+/// use std::borrow::Cow;
+///
+/// use id_newtype::{Delimiter, Qualified};
+///
+/// #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// pub struct MyId(Cow<'static, str>);
+///
+/// id_newtype::id_newtype!(MyId, MyIdInvalidFmt);
+///
+/// pub enum Slash {}
+///
+/// impl Delimiter for Slash {
+///     const DELIMITER: &'static str = "/";
+/// }
+///
+/// let item: Qualified<MyId, MyId, Slash> = "my_plugin/item".parse().unwrap();
+/// assert_eq!("my_plugin", item.namespace().as_str());
+/// ```
+///
+/// [`Qualified`]: crate::Qualified
+pub trait Delimiter: 'static {
+    /// String between the namespace and local parts, e.g. `"::"`.
+    ///
+    /// This must not be empty, and its first character must not be allowed in
+    /// the namespace type.
+    const DELIMITER: &'static str;
+}
+
+/// The `::` delimiter, e.g. `my_plugin::item`.
+pub enum DoubleColon {}
+
+impl Delimiter for DoubleColon {
+    const DELIMITER: &'static str = "::";
+}
@@ -0,0 +1,594 @@
+use std::{
+    borrow::{Borrow, Cow},
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ops::Deref,
+    str::FromStr,
+};
+
+use crate::{IdDecodeError, IdKind, IdNewtype, IdRules, IdViolation, Intern, Interner, InvalidId};
+
+/// Generic ID of kind `K`, as an alternative to declaring a type with
+/// `id_newtype!`.
+///
+/// Each kind is a marker type that implements [`IdKind`], usually declared
+/// with `id_kind!`, so `Id<User>` and `Id<Order>` are distinct types that
+/// share one implementation. `Id` has the same API as the
+/// lifetime-parameterized types generated by `id_newtype!`, with [`InvalidId`]
+/// as its error type.
+///
+/// ```rust
+/// use id_newtype::{Id, InvalidId};
+///
+/// id_newtype::id_kind!(pub User);
+/// id_newtype::id_kind!(pub Order);
+///
+/// const ALICE: Id<'static, User> = Id::new_const("alice");
+///
+/// let order_id: Id<'_, Order> = "order_1".parse().unwrap();
+/// let error: InvalidId<'_, User> = Id::<User>::new("a b").unwrap_err();
+///
+/// assert_eq!("alice", ALICE.as_str());
+/// assert_eq!("order_1", order_id.as_str());
+/// assert_eq!(
+///     "`a b` is not a valid `User`: invalid character ` ` at column 2.",
+///     error.to_string().lines().next().unwrap()
+/// );
+/// ```
+pub struct Id<'s, K> {
+    /// String held by this ID.
+    value: Cow<'s, str>,
+    /// Marker for the ID kind.
+    marker: PhantomData<fn() -> K>,
+}
+
+impl<'s, K> Id<'s, K> {
+    /// Returns a new `Id` without verification.
+    ///
+    /// This is here for guaranteed valid usage such as being called from
+    /// `new_const`.
+    #[doc(hidden)]
+    pub const fn new_unchecked(s: &'s str) -> Self {
+        Self::from_cow(Cow::Borrowed(s))
+    }
+
+    /// Returns the inner `Cow<'s, str>`.
+    pub fn into_inner(self) -> Cow<'s, str> {
+        self.value
+    }
+
+    /// Returns this with owned data.
+    pub fn into_static(self) -> Id<'static, K> {
+        Id::from_cow(Cow::Owned(self.value.into_owned()))
+    }
+
+    /// Returns the `&str` held by this ID.
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns an `Id` holding the given value, without verification.
+    const fn from_cow(value: Cow<'s, str>) -> Self {
+        Self {
+            value,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'s, K> Id<'s, K>
+where
+    K: IdKind,
+{
+    /// Regex that matches valid `Id`s of this kind, e.g. for JSON Schema and
+    /// OpenAPI documents.
+    ///
+    /// Reserved words and reserved prefixes are not expressed in the pattern.
+    /// See `IdRules::pattern` for details.
+    pub const PATTERN: &'static str = {
+        let (pattern, _) = Self::PATTERN_BYTES.split_at(K::RULES.pattern_len());
+        match std::str::from_utf8(pattern) {
+            Ok(pattern) => pattern,
+            Err(_) => panic!("Patterns only contain ASCII characters."),
+        }
+    };
+    /// Bytes of the pattern, followed by zeros.
+    ///
+    /// The pattern's length cannot be used as an array length in a generic
+    /// `impl`, so this has the maximum length of a pattern.
+    const PATTERN_BYTES: [u8; IdRules::PATTERN_MAX_LEN] = K::RULES.pattern_bytes();
+    /// Rules that a valid `Id` of this kind must satisfy.
+    pub const RULES: IdRules = K::RULES;
+
+    /// Returns a new `Id` if the given `&str` is valid.
+    pub fn new(s: &'s str) -> Result<Self, InvalidId<'s, K>> {
+        Self::try_from(s)
+    }
+
+    /// Returns whether the provided `&str` is a valid `Id` of this kind.
+    pub const fn is_valid_id(proposed_id: &str) -> bool {
+        Self::validate(proposed_id).is_ok()
+    }
+
+    /// Returns the first violation of this kind's rules in the provided
+    /// `&str`, if any.
+    pub const fn validate(proposed_id: &str) -> Result<(), IdViolation> {
+        K::RULES.validate(proposed_id)
+    }
+
+    /// Returns a new `Id`, checked when evaluated in a `const` context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given `&str` is not valid. When used in a `const`, this
+    /// is a compile error.
+    #[track_caller]
+    pub const fn new_const(s: &'s str) -> Self {
+        match Self::validate(s) {
+            Ok(()) => Self::new_unchecked(s),
+            Err(violation) => violation.panic(K::TYPE_NAME, s),
+        }
+    }
+
+    /// Returns an `Id` built from arbitrary text, replacing or removing
+    /// characters that are not allowed.
+    ///
+    /// This never fails. See `IdRules::sanitize` for how the text is changed.
+    pub fn from_lossy(s: &str) -> Self {
+        let sanitized = K::RULES.sanitize_valid(s, K::TYPE_NAME, |id| Self::validate(id).is_ok());
+        Self::from_cow(Cow::Owned(sanitized))
+    }
+
+    /// Returns an `Id` that losslessly encodes arbitrary text, escaping
+    /// characters that are not allowed.
+    ///
+    /// Distinct values always have distinct encodings, and `decode` returns
+    /// the original value. See `IdRules::encode` for the escape scheme.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the encoded value does not satisfy the rules, e.g.
+    /// if it is empty or too long.
+    pub fn encode(s: &str) -> Result<Self, InvalidId<'static, K>> {
+        Self::try_from(K::RULES.encode(s))
+    }
+
+    /// Returns the original value of an ID produced by `encode`.
+    pub fn decode(&self) -> Result<String, IdDecodeError> {
+        IdRules::decode(&self.value)
+    }
+}
+
+// These are implemented manually so that `K` does not need to implement them.
+
+impl<K> Clone for Id<'_, K> {
+    fn clone(&self) -> Self {
+        Self::from_cow(self.value.clone())
+    }
+}
+
+impl<K> PartialEq for Id<'_, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<K> Eq for Id<'_, K> {}
+
+impl<K> PartialOrd for Id<'_, K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K> Ord for Id<'_, K> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl<K> Hash for Id<'_, K> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+impl<K> fmt::Debug for Id<'_, K>
+where
+    K: IdKind,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Id<{}>({:?})", K::TYPE_NAME, self.value)
+    }
+}
+
+impl<K> fmt::Display for Id<'_, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+impl<'s, K> Deref for Id<'s, K> {
+    type Target = Cow<'s, str>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<K> TryFrom<String> for Id<'_, K>
+where
+    K: IdKind,
+{
+    type Error = InvalidId<'static, K>;
+
+    fn try_from(s: String) -> Result<Self, InvalidId<'static, K>> {
+        match Self::validate(&s) {
+            Ok(()) => Ok(Self::from_cow(Cow::Owned(s))),
+            Err(violation) => Err(InvalidId::new(Cow::Owned(s), violation)),
+        }
+    }
+}
+
+impl<'s, K> TryFrom<&'s str> for Id<'s, K>
+where
+    K: IdKind,
+{
+    type Error = InvalidId<'s, K>;
+
+    fn try_from(s: &'s str) -> Result<Self, InvalidId<'s, K>> {
+        match Self::validate(s) {
+            Ok(()) => Ok(Self::new_unchecked(s)),
+            Err(violation) => Err(InvalidId::new(Cow::Borrowed(s), violation)),
+        }
+    }
+}
+
+impl<K> FromStr for Id<'_, K>
+where
+    K: IdKind,
+{
+    type Err = InvalidId<'static, K>;
+
+    fn from_str(s: &str) -> Result<Self, InvalidId<'static, K>> {
+        match Self::validate(s) {
+            Ok(()) => Ok(Self::from_cow(Cow::Owned(String::from(s)))),
+            Err(violation) => {
+                let s = Cow::Owned(String::from(violation.error_value(s)));
+                Err(InvalidId::new(s, violation))
+            }
+        }
+    }
+}
+
+impl<K> AsRef<str> for Id<'_, K> {
+    fn as_ref(&self) -> &str {
+        &self.value
+    }
+}
+
+impl<K> Borrow<str> for Id<'_, K> {
+    fn borrow(&self) -> &str {
+        &self.value
+    }
+}
+
+impl<K> Borrow<str> for &Id<'_, K> {
+    fn borrow(&self) -> &str {
+        &self.value
+    }
+}
+
+impl<K> Intern for Id<'_, K>
+where
+    K: IdKind,
+{
+    type Static = Id<'static, K>;
+
+    fn interner() -> &'static Interner {
+        K::interner()
+    }
+
+    fn from_interned(s: &'static str) -> Self {
+        Self::new_unchecked(s)
+    }
+}
+
+impl<'s, K> IdNewtype for Id<'s, K>
+where
+    K: IdKind,
+{
+    type Error = InvalidId<'static, K>;
+    type Inner = Cow<'s, str>;
+
+    const PATTERN: &'static str = Id::<'s, K>::PATTERN;
+    const RULES: IdRules = K::RULES;
+    const TYPE_NAME: &'static str = K::TYPE_NAME;
+
+    fn validate(proposed_id: &str) -> Result<(), IdViolation> {
+        Id::<K>::validate(proposed_id)
+    }
+
+    fn try_from_str(s: &str) -> Result<Self, InvalidId<'static, K>> {
+        s.parse()
+    }
+
+    fn try_from_string(s: String) -> Result<Self, InvalidId<'static, K>> {
+        Self::try_from(s)
+    }
+
+    fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    fn into_inner(self) -> Cow<'s, str> {
+        self.value
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<K> serde::Serialize for Id<'_, K> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.value)
+    }
+}
+
+// Borrowed strings are deserialized as `Cow::Borrowed` without copying.
+#[cfg(feature = "serde")]
+impl<'de: 's, 's, K> serde::Deserialize<'de> for Id<'s, K>
+where
+    K: IdKind,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct IdVisitor<'s, K>(PhantomData<Id<'s, K>>);
+
+        impl<'de: 's, 's, K> serde::de::Visitor<'de> for IdVisitor<'s, K>
+        where
+            K: IdKind,
+        {
+            type Value = Id<'s, K>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a `{}` string", K::TYPE_NAME)
+            }
+
+            fn visit_borrowed_str<E>(self, s: &'de str) -> Result<Id<'s, K>, E>
+            where
+                E: serde::de::Error,
+            {
+                Id::try_from(s).map_err(E::custom)
+            }
+
+            fn visit_str<E>(self, s: &str) -> Result<Id<'s, K>, E>
+            where
+                E: serde::de::Error,
+            {
+                s.parse().map_err(E::custom)
+            }
+
+            fn visit_string<E>(self, s: String) -> Result<Id<'s, K>, E>
+            where
+                E: serde::de::Error,
+            {
+                Id::try_from(s).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(IdVisitor(PhantomData))
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl<K> schemars::JsonSchema for Id<'_, K>
+where
+    K: IdKind,
+{
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed(K::TYPE_NAME)
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        Cow::Borrowed(std::any::type_name::<K>())
+    }
+
+    fn json_schema(_generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        let mut schema = schemars::json_schema!({
+            "type": "string",
+            "pattern": Self::PATTERN,
+        });
+        if let Some(min_len) = K::RULES.min_len_limit() {
+            schema.insert(String::from("minLength"), min_len.into());
+        }
+        if let Some(max_len) = K::RULES.max_len_limit() {
+            schema.insert(String::from("maxLength"), max_len.into());
+        }
+        schema
+    }
+}
+
+#[cfg(feature = "utoipa")]
+impl<K> utoipa::PartialSchema for Id<'_, K>
+where
+    K: IdKind,
+{
+    fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+        utoipa::openapi::schema::ObjectBuilder::new()
+            .schema_type(utoipa::openapi::schema::Type::String)
+            .pattern(Some(Self::PATTERN))
+            .min_length(K::RULES.min_len_limit())
+            .max_length(K::RULES.max_len_limit())
+            .into()
+    }
+}
+
+#[cfg(feature = "utoipa")]
+impl<K> utoipa::ToSchema for Id<'_, K>
+where
+    K: IdKind,
+{
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed(K::TYPE_NAME)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        borrow::Cow,
+        collections::{hash_map::DefaultHasher, HashSet},
+        hash::{Hash, Hasher},
+    };
+
+    use super::Id;
+    use crate::{IdKind, IdNewtype, Intern, InvalidReason};
+
+    crate::id_kind!(User);
+    crate::id_kind!(K8sName; first = "a-z", rest = "a-z0-9-", max_len = 8);
+
+    #[test]
+    fn new() {
+        let user_id = Id::<User>::new("alice").unwrap();
+
+        assert_eq!("alice", user_id.as_str());
+        assert!(matches!(user_id.into_inner(), Cow::Borrowed("alice")));
+        assert_eq!("User", User::TYPE_NAME);
+    }
+
+    #[test]
+    fn new_invalid() {
+        let error = Id::<K8sName>::new("Web").unwrap_err();
+
+        assert_eq!(InvalidReason::InvalidFirstChar, error.reason());
+        assert_eq!(Some('W'), error.invalid_char());
+        assert_eq!(&Cow::Borrowed("Web"), error.value());
+        assert_eq!(
+            "`Web` is not a valid `K8sName`: invalid first character `W` at column 1.\n    \
+            Web\n    \
+            ^\n\
+            `K8sName`s must begin with a lowercase letter, and contain only lowercase letters, \
+            numbers, or hyphens, and be at most 8 characters long.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn new_const() {
+        const ALICE: Id<'static, User> = Id::new_const("alice");
+
+        assert_eq!("alice", ALICE.as_str());
+    }
+
+    #[test]
+    #[should_panic(expected = "`a b` is not a valid `User`: invalid character ` ` at column 2.")]
+    fn new_const_invalid_panics_at_runtime() {
+        let _ = Id::<User>::new_const(std::hint::black_box("a b"));
+    }
+
+    #[test]
+    fn try_from_and_from_str() {
+        let owned = Id::<User>::try_from(String::from("alice")).unwrap();
+        let parsed = "alice".parse::<Id<'_, User>>().unwrap();
+
+        assert_eq!(owned, parsed);
+        assert!(matches!(parsed.into_inner(), Cow::Owned(_)));
+        assert_eq!(
+            InvalidReason::TooLong,
+            "web-server-1"
+                .parse::<Id<'_, K8sName>>()
+                .unwrap_err()
+                .reason()
+        );
+    }
+
+    #[test]
+    fn from_lossy_encode_decode() {
+        assert_eq!("web-app", Id::<K8sName>::from_lossy("Web App").as_str());
+
+        let encoded = Id::<User>::encode("a/b").unwrap();
+        assert_eq!("a_2F_b", encoded.as_str());
+        assert_eq!(Ok(String::from("a/b")), encoded.decode());
+    }
+
+    #[test]
+    fn into_static() {
+        let s = String::from("alice");
+        let user_id = Id::<User>::new(&s).unwrap().into_static();
+        drop(s);
+
+        assert_eq!("alice", user_id.as_str());
+    }
+
+    #[test]
+    fn hash_matches_str() {
+        fn hash<T: Hash + ?Sized>(value: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let user_ids = HashSet::from([Id::<User>::new_unchecked("alice")]);
+
+        assert_eq!(hash("alice"), hash(&Id::<User>::new_unchecked("alice")));
+        assert!(user_ids.contains("alice"));
+    }
+
+    #[test]
+    fn debug_and_display() {
+        let user_id = Id::<User>::new_unchecked("alice");
+
+        assert_eq!(r#"Id<User>("alice")"#, format!("{user_id:?}"));
+        assert_eq!("alice", user_id.to_string());
+    }
+
+    #[test]
+    fn pattern() {
+        assert_eq!("^[A-Za-z_][A-Za-z0-9_]*$", Id::<User>::PATTERN);
+        assert_eq!("^[a-z][a-z0-9\\-]{0,7}$", Id::<K8sName>::PATTERN);
+        assert_eq!(K8sName::RULES.pattern(), <Id<K8sName>>::PATTERN);
+    }
+
+    #[test]
+    fn interned_kinds_have_separate_interners() {
+        let user = Id::<User>::new_unchecked("web").intern();
+        let k8s_name = Id::<K8sName>::new_unchecked("db").intern();
+
+        assert!(!std::ptr::eq(User::interner(), K8sName::interner()));
+        assert_eq!(Some(user.index()), User::interner().get("web"));
+        assert_eq!(Some(k8s_name.index()), K8sName::interner().get("db"));
+        assert_eq!(Id::<User>::new_unchecked("web"), user.to_id());
+        assert_eq!("db", k8s_name.resolve());
+    }
+
+    #[test]
+    fn id_newtype_trait() {
+        fn load<T: IdNewtype>(s: &str) -> Result<T, T::Error> {
+            T::try_from_str(s)
+        }
+
+        assert_eq!("K8sName", <Id<'_, K8sName> as IdNewtype>::TYPE_NAME);
+        assert!(load::<Id<'_, K8sName>>("web").is_ok());
+        assert!(load::<Id<'_, K8sName>>("web_server").is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let json = String::from(r#""alice""#);
+        let user_id: Id<'_, User> = serde_json::from_str(&json).unwrap();
+
+        assert!(matches!(
+            user_id.clone().into_inner(),
+            Cow::Borrowed("alice")
+        ));
+        assert_eq!(json, serde_json::to_string(&user_id).unwrap());
+        assert!(serde_json::from_str::<Id<'_, K8sName>>(r#""Web""#).is_err());
+    }
+}
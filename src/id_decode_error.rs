@@ -0,0 +1,41 @@
+use std::fmt;
+
+/// Error decoding an ID that was produced by [`IdRules::encode`].
+///
+/// [`IdRules::encode`]: crate::IdRules::encode
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IdDecodeError {
+    /// The escape sequence that could not be decoded, e.g. `_D800_`.
+    escape: String,
+    /// Byte offset of the escape sequence in the ID.
+    offset: usize,
+}
+
+impl IdDecodeError {
+    /// Returns a new `IdDecodeError`.
+    pub fn new(escape: String, offset: usize) -> Self {
+        Self { escape, offset }
+    }
+
+    /// Returns the escape sequence that could not be decoded.
+    pub fn escape(&self) -> &str {
+        &self.escape
+    }
+
+    /// Returns the byte offset of the escape sequence in the ID.
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl fmt::Display for IdDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` at offset {} is not a valid escaped character.",
+            self.escape, self.offset
+        )
+    }
+}
+
+impl std::error::Error for IdDecodeError {}
@@ -0,0 +1,77 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::IdNewtype;
+
+/// Guard returned by [`IdNewtype::edit`], which holds the value of an ID as a
+/// `String` to be changed.
+///
+/// The `String` is written back to the ID by [`IdEdit::commit`], if it is
+/// valid. If the guard is dropped without being committed, the ID is
+/// unchanged.
+///
+/// ```rust
+/// use std::borrow::Cow;
+///
+/// use id_newtype::IdNewtype;
+///
+/// #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// pub struct MyId(Cow<'static, str>);
+///
+/// id_newtype::id_newtype!(MyId, MyIdInvalidFmt);
+///
+/// let mut my_id = MyId::new("web").unwrap();
+///
+/// let mut edit = my_id.edit();
+/// edit.make_ascii_uppercase();
+/// edit.commit().unwrap();
+/// assert_eq!("WEB", my_id.as_str());
+///
+/// let mut edit = my_id.edit();
+/// edit.push_str(" server");
+/// assert!(edit.commit().is_err());
+/// assert_eq!("WEB", my_id.as_str());
+/// ```
+#[derive(Debug)]
+#[must_use = "Edits are discarded unless `commit` is called."]
+pub struct IdEdit<'id, T> {
+    /// The ID to write the value back to.
+    id: &'id mut T,
+    /// The value being edited.
+    value: String,
+}
+
+impl<'id, T> IdEdit<'id, T>
+where
+    T: IdNewtype,
+{
+    /// Returns a new `IdEdit` holding a copy of the ID's value.
+    pub fn new(id: &'id mut T) -> Self {
+        let value = String::from(id.as_str());
+        Self { id, value }
+    }
+
+    /// Writes the edited value back to the ID, if it is valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, leaving the ID unchanged, if the edited value is not
+    /// valid.
+    pub fn commit(self) -> Result<(), T::Error> {
+        *self.id = T::try_from_string(self.value)?;
+        Ok(())
+    }
+}
+
+impl<T> Deref for IdEdit<'_, T> {
+    type Target = String;
+
+    fn deref(&self) -> &String {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for IdEdit<'_, T> {
+    fn deref_mut(&mut self) -> &mut String {
+        &mut self.value
+    }
+}
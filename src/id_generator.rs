@@ -0,0 +1,526 @@
+use std::{
+    fmt,
+    marker::PhantomData,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{rng::Rng, CharClass, IdGeneratorError, IdNewtype};
+
+/// Generates fresh IDs of type `T`.
+///
+/// Three strategies are supported:
+///
+/// * [`sequential`]: A prefix followed by a counter, e.g. `item_1`, `item_2`,
+///   which is deterministic and useful in tests.
+/// * [`random`]: A fixed number of characters drawn from `[A-Za-z0-9_]`.
+/// * [`time_sortable`]: A prefix followed by a ULID-like value, which sorts in
+///   the order the IDs were generated.
+///
+/// Each constructor checks that the strategy produces a valid ID for `T`, and
+/// characters are only drawn from those allowed by `T`'s rules. Random IDs
+/// also follow the underscore rules of a `snake_case` or
+/// `SCREAMING_SNAKE_CASE` style. [`next_id`] returns an error once the
+/// generator is exhausted, e.g. when sequential IDs no longer fit in the
+/// maximum length:
+///
+/// ```rust
+/// use std::borrow::Cow;
+///
+/// use id_newtype::IdGenerator;
+///
+/// #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// pub struct MyId(Cow<'static, str>);
+///
+/// id_newtype::id_newtype!(MyId, MyIdInvalidFmt);
+///
+/// let mut generator = IdGenerator::<MyId>::sequential("item_").unwrap();
+/// assert_eq!("item_1", generator.next_id().unwrap().as_str());
+/// assert_eq!("item_2", generator.next_id().unwrap().as_str());
+///
+/// let events = IdGenerator::<MyId>::time_sortable("evt_").unwrap();
+/// let ids = events.take(3).collect::<Vec<_>>();
+/// assert!(ids.is_sorted_by_key(MyId::as_str));
+///
+/// // IDs may not begin with a digit.
+/// assert!(IdGenerator::<MyId>::sequential("").is_err());
+/// ```
+///
+/// Generators also implement `Iterator`, which ends when [`next_id`] returns
+/// an error.
+///
+/// [`next_id`]: Self::next_id
+/// [`sequential`]: Self::sequential
+/// [`random`]: Self::random
+/// [`time_sortable`]: Self::time_sortable
+pub struct IdGenerator<T> {
+    /// How IDs are generated.
+    strategy: Strategy,
+    /// Marker for the ID type.
+    marker: PhantomData<fn() -> T>,
+}
+
+/// How an [`IdGenerator`] builds IDs.
+#[derive(Clone, Debug)]
+enum Strategy {
+    /// Prefix followed by a counter.
+    Sequential {
+        /// Text before the counter.
+        prefix: String,
+        /// Next value of the counter.
+        counter: u64,
+    },
+    /// Fixed number of random characters.
+    Random {
+        /// Characters that the first character is drawn from.
+        first: Vec<char>,
+        /// Characters that the remaining characters are drawn from.
+        rest: Vec<char>,
+        /// Characters that the character after a `_`, and the last character,
+        /// are drawn from, if the style forbids repeated and trailing
+        /// underscores.
+        rest_without_underscore: Option<Vec<char>>,
+        /// Number of characters in each ID.
+        len: usize,
+        /// Source of randomness.
+        rng: Rng,
+    },
+    /// Prefix followed by a 48 bit millisecond timestamp and 80 random bits,
+    /// encoded in Crockford's base 32.
+    TimeSortable {
+        /// Text before the encoded value.
+        prefix: String,
+        /// Base 32 digits in ascending order.
+        alphabet: &'static str,
+        /// Returns the milliseconds since the Unix epoch.
+        clock: fn() -> u64,
+        /// Source of randomness.
+        rng: Rng,
+        /// Previously encoded value, which later values must be greater than.
+        last: Option<u128>,
+    },
+}
+
+impl<T> IdGenerator<T>
+where
+    T: IdNewtype,
+{
+    /// Number of consecutive rejected IDs after which `next_id` returns an
+    /// error.
+    const MAX_ATTEMPTS: usize = 100;
+    /// Characters that random IDs are drawn from.
+    pub const RANDOM_ALPHABET: &'static str =
+        "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789_";
+
+    /// Returns a generator of `prefix` followed by a counter starting at 1.
+    pub fn sequential(prefix: &str) -> Result<Self, IdGeneratorError<T::Error>> {
+        Self::with_strategy(Strategy::Sequential {
+            prefix: String::from(prefix),
+            counter: 1,
+        })
+    }
+
+    /// Returns a generator of `len` random characters from
+    /// [`RANDOM_ALPHABET`], restricted to the characters that `T` allows.
+    ///
+    /// The randomness is not cryptographically secure.
+    ///
+    /// [`RANDOM_ALPHABET`]: Self::RANDOM_ALPHABET
+    pub fn random(len: usize) -> Result<Self, IdGeneratorError<T::Error>> {
+        Self::random_with_rng(len, Rng::new())
+    }
+
+    /// Returns a generator of `len` random characters, which always produces
+    /// the same IDs for the same seed.
+    pub fn random_seeded(len: usize, seed: u64) -> Result<Self, IdGeneratorError<T::Error>> {
+        Self::random_with_rng(len, Rng::with_seed(seed))
+    }
+
+    /// Returns a generator of `prefix` followed by a ULID-like value, using
+    /// the system clock.
+    ///
+    /// The value is 26 characters of Crockford's base 32, in uppercase if `T`
+    /// allows it, otherwise in lowercase. IDs from the same generator are
+    /// always greater than the previous ID.
+    pub fn time_sortable(prefix: &str) -> Result<Self, IdGeneratorError<T::Error>> {
+        Self::time_sortable_with_clock(prefix, system_clock)
+    }
+
+    /// Returns a generator of `prefix` followed by a ULID-like value, using
+    /// the given clock.
+    ///
+    /// `clock` returns the milliseconds since the Unix epoch. This is useful
+    /// for platforms without `SystemTime`, and for tests.
+    pub fn time_sortable_with_clock(
+        prefix: &str,
+        clock: fn() -> u64,
+    ) -> Result<Self, IdGeneratorError<T::Error>> {
+        const UPPER: &str = "0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+        const LOWER: &str = "0123456789abcdefghjkmnpqrstvwxyz";
+
+        let rest = T::RULES.rest_class();
+        let alphabet = [UPPER, LOWER]
+            .into_iter()
+            .find(|alphabet| alphabet.chars().all(|c| rest.contains(c)))
+            .ok_or(IdGeneratorError::NoAllowedChars { alphabet: UPPER })?;
+
+        Self::with_strategy(Strategy::TimeSortable {
+            prefix: String::from(prefix),
+            alphabet,
+            clock,
+            rng: Rng::new(),
+            last: None,
+        })
+    }
+
+    /// Returns the next ID.
+    ///
+    /// IDs that are rejected by `T`'s reserved words or predicate are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IdGeneratorError::Exhausted`] if a sequential generator's IDs
+    /// no longer fit in `T`'s maximum length, or if 100 consecutive IDs are
+    /// rejected.
+    pub fn next_id(&mut self) -> Result<T, IdGeneratorError<T::Error>> {
+        let mut attempts = 0;
+        loop {
+            let candidate = self.strategy.next_candidate();
+            let error = match T::try_from_str(&candidate) {
+                Ok(id) => return Ok(id),
+                Err(error) => error,
+            };
+
+            attempts += 1;
+            let is_too_long = T::RULES
+                .max_len_limit()
+                .is_some_and(|max_len| candidate.len() > max_len);
+            if is_too_long || attempts == Self::MAX_ATTEMPTS {
+                return Err(IdGeneratorError::Exhausted(error));
+            }
+        }
+    }
+
+    fn random_with_rng(len: usize, rng: Rng) -> Result<Self, IdGeneratorError<T::Error>> {
+        let allowed = |char_class: &CharClass| {
+            Self::RANDOM_ALPHABET
+                .chars()
+                .filter(|c| char_class.contains(*c))
+                .collect::<Vec<_>>()
+        };
+        let first = allowed(T::RULES.first_class());
+        let rest = allowed(T::RULES.rest_class());
+        let rest_without_underscore = T::RULES
+            .case_style()
+            .is_some_and(|style| style.has_underscore_rules())
+            .then(|| {
+                rest.iter()
+                    .copied()
+                    .filter(|c| *c != '_')
+                    .collect::<Vec<_>>()
+            });
+        let rest_len = rest_without_underscore.as_ref().unwrap_or(&rest).len();
+        if first.is_empty() || (len > 1 && rest_len == 0) {
+            return Err(IdGeneratorError::NoAllowedChars {
+                alphabet: Self::RANDOM_ALPHABET,
+            });
+        }
+
+        Self::with_strategy(Strategy::Random {
+            first,
+            rest,
+            rest_without_underscore,
+            len,
+            rng,
+        })
+    }
+
+    /// Returns a generator with the given strategy, if the first ID that it
+    /// would produce is valid.
+    fn with_strategy(strategy: Strategy) -> Result<Self, IdGeneratorError<T::Error>> {
+        let sample = strategy.clone().next_candidate();
+        T::try_from_str(&sample).map_err(IdGeneratorError::InvalidId)?;
+
+        Ok(Self {
+            strategy,
+            marker: PhantomData,
+        })
+    }
+}
+
+impl Strategy {
+    /// Returns the next ID string, which may not be valid.
+    fn next_candidate(&mut self) -> String {
+        match self {
+            Self::Sequential { prefix, counter } => {
+                let candidate = format!("{prefix}{counter}");
+                *counter += 1;
+                candidate
+            }
+            Self::Random {
+                first,
+                rest,
+                rest_without_underscore,
+                len,
+                rng,
+            } => {
+                let mut candidate = String::with_capacity(*len);
+                for index in 0..*len {
+                    let chars = match rest_without_underscore {
+                        _ if index == 0 => &*first,
+                        Some(rest_without_underscore)
+                            if candidate.ends_with('_') || index + 1 == *len =>
+                        {
+                            &*rest_without_underscore
+                        }
+                        _ => &*rest,
+                    };
+                    candidate.push(chars[rng.index(chars.len())]);
+                }
+                candidate
+            }
+            Self::TimeSortable {
+                prefix,
+                alphabet,
+                clock,
+                rng,
+                last,
+            } => {
+                let timestamp = u128::from(clock()) & ((1 << 48) - 1);
+                let random = (u128::from(rng.next_u64()) << 16) | u128::from(rng.next_u64() >> 48);
+                let value = match *last {
+                    Some(last) if timestamp <= last >> 80 => last + 1,
+                    _ => (timestamp << 80) | random,
+                };
+                *last = Some(value);
+
+                let digits = alphabet.as_bytes();
+                let mut candidate = prefix.clone();
+                candidate.extend((0..26).map(|index| {
+                    let digit = (value >> (125 - 5 * index)) & 0x1F;
+                    char::from(digits[digit as usize])
+                }));
+                candidate
+            }
+        }
+    }
+}
+
+impl<T> Iterator for IdGenerator<T>
+where
+    T: IdNewtype,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.next_id().ok()
+    }
+}
+
+impl<T> fmt::Debug for IdGenerator<T>
+where
+    T: IdNewtype,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdGenerator")
+            .field("type_name", &T::TYPE_NAME)
+            .field("strategy", &self.strategy)
+            .finish()
+    }
+}
+
+/// Returns the milliseconds since the Unix epoch from the system clock.
+fn system_clock() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::IdGenerator;
+    use crate::{IdGeneratorError, InvalidReason};
+
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    pub struct MyId(Cow<'static, str>);
+
+    crate::id_newtype!(MyId, MyIdInvalidFmt);
+
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    pub struct K8sName(Cow<'static, str>);
+
+    crate::id_newtype!(K8sName, K8sNameInvalidFmt; first = "a-z", rest = "a-z0-9-", max_len = 32);
+
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    pub struct ShortId(Cow<'static, str>);
+
+    crate::id_newtype!(ShortId, ShortIdInvalidFmt; max_len = 6, reserved = ["item_2"]);
+
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    pub struct SnakeId(Cow<'static, str>);
+
+    crate::id_newtype!(SnakeId, SnakeIdInvalidFmt; style = Snake);
+
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    pub struct FirstOnlyId(Cow<'static, str>);
+
+    crate::id_newtype!(FirstOnlyId, FirstOnlyIdInvalidFmt; predicate = self::is_a1);
+
+    const fn is_a1(proposed_id: &str) -> bool {
+        matches!(proposed_id.as_bytes(), b"a1")
+    }
+
+    #[test]
+    fn sequential() {
+        let generator = IdGenerator::<MyId>::sequential("item_").unwrap();
+
+        assert_eq!(
+            vec!["item_1", "item_2", "item_3"],
+            generator
+                .take(3)
+                .map(|id| id.as_str().to_string())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn sequential_skips_reserved_ids() {
+        let mut generator = IdGenerator::<ShortId>::sequential("item_").unwrap();
+
+        assert_eq!("item_1", generator.next_id().unwrap().as_str());
+        assert_eq!("item_3", generator.next_id().unwrap().as_str());
+    }
+
+    #[test]
+    fn sequential_when_exhausted_is_error() {
+        let mut generator = IdGenerator::<ShortId>::sequential("item_").unwrap();
+
+        assert_eq!(8, generator.by_ref().count());
+        let Err(IdGeneratorError::Exhausted(error)) = generator.next_id() else {
+            panic!("Expected the generator to be exhausted.");
+        };
+        assert_eq!(InvalidReason::TooLong, error.reason());
+        assert!(IdGeneratorError::Exhausted(error)
+            .to_string()
+            .starts_with("The generator is exhausted: `item_1...` is not a valid `ShortId`"));
+    }
+
+    #[test]
+    fn rejected_ids_exhaust_generator() {
+        let mut generator = IdGenerator::<FirstOnlyId>::sequential("a").unwrap();
+
+        assert_eq!("a1", generator.next_id().unwrap().as_str());
+        let Err(IdGeneratorError::Exhausted(error)) = generator.next_id() else {
+            panic!("Expected the generator to be exhausted.");
+        };
+        assert_eq!("a101", error.value());
+        assert_eq!(InvalidReason::Predicate, error.reason());
+    }
+
+    #[test]
+    fn sequential_with_invalid_prefix_is_error() {
+        let Err(IdGeneratorError::InvalidId(error)) = IdGenerator::<MyId>::sequential("my item")
+        else {
+            panic!("Expected the prefix to be invalid.");
+        };
+
+        assert_eq!(InvalidReason::InvalidChar, error.reason());
+        assert!(IdGenerator::<ShortId>::sequential("items_").is_err());
+    }
+
+    #[test]
+    fn random() {
+        let ids = IdGenerator::<K8sName>::random(12)
+            .unwrap()
+            .take(20)
+            .collect::<Vec<_>>();
+
+        assert!(ids.iter().all(|id| id.as_str().len() == 12));
+        assert!(ids
+            .iter()
+            .all(|id| id.as_str().starts_with(|c: char| c.is_ascii_lowercase())));
+        assert!(ids
+            .iter()
+            .flat_map(|id| id.as_str().chars())
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()));
+    }
+
+    #[test]
+    fn random_follows_underscore_rules() {
+        let ids = IdGenerator::<SnakeId>::random_seeded(4, 1)
+            .unwrap()
+            .take(1000)
+            .collect::<Vec<_>>();
+
+        assert_eq!(1000, ids.len());
+        assert!(ids.iter().all(|id| !id.as_str().contains("__")));
+        assert!(ids.iter().all(|id| !id.as_str().ends_with('_')));
+    }
+
+    #[test]
+    fn random_seeded_is_deterministic() {
+        let ids = |seed| {
+            IdGenerator::<MyId>::random_seeded(8, seed)
+                .unwrap()
+                .take(3)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(ids(1), ids(1));
+        assert_ne!(ids(1), ids(2));
+    }
+
+    #[test]
+    fn random_with_invalid_length_is_error() {
+        let Err(IdGeneratorError::InvalidId(error)) = IdGenerator::<ShortId>::random(7) else {
+            panic!("Expected the length to be invalid.");
+        };
+
+        assert_eq!(InvalidReason::TooLong, error.reason());
+        assert!(IdGenerator::<MyId>::random(0).is_err());
+    }
+
+    #[test]
+    fn time_sortable() {
+        let ids = IdGenerator::<MyId>::time_sortable_with_clock("evt_", || 1_700_000_000_000)
+            .unwrap()
+            .take(3)
+            .collect::<Vec<_>>();
+
+        assert!(ids.iter().all(|id| id.as_str().len() == 30));
+        assert!(ids.iter().all(|id| id.as_str().starts_with("evt_01HF")));
+        assert!(ids.is_sorted_by_key(|id| id.as_str().to_string()));
+        assert_ne!(ids[0], ids[1]);
+    }
+
+    #[test]
+    fn time_sortable_uses_lowercase_when_uppercase_is_not_allowed() {
+        let id = IdGenerator::<K8sName>::time_sortable("evt-")
+            .unwrap()
+            .next_id()
+            .unwrap();
+
+        assert!(id.as_str().starts_with("evt-0"));
+        assert!(id.as_str().chars().all(|c| !c.is_ascii_uppercase()));
+    }
+
+    #[test]
+    fn time_sortable_with_invalid_prefix_is_error() {
+        // IDs may not begin with a digit.
+        assert!(IdGenerator::<MyId>::time_sortable("").is_err());
+
+        #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+        pub struct LowerId(Cow<'static, str>);
+
+        crate::id_newtype!(LowerId, LowerIdInvalidFmt; first = "a-z", rest = "a-z");
+
+        assert_eq!(
+            Some(IdGeneratorError::NoAllowedChars {
+                alphabet: "0123456789ABCDEFGHJKMNPQRSTVWXYZ"
+            }),
+            IdGenerator::<LowerId>::time_sortable("evt").err()
+        );
+    }
+}
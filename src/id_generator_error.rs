@@ -0,0 +1,49 @@
+use std::{error::Error, fmt};
+
+/// Error creating an [`IdGenerator`] or generating an ID, where `E` is the ID
+/// type's error.
+///
+/// [`IdGenerator`]: crate::IdGenerator
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IdGeneratorError<E> {
+    /// The generator would produce invalid IDs, e.g. because the prefix or
+    /// length is not allowed.
+    InvalidId(E),
+    /// None of the characters that the generator draws from are allowed.
+    NoAllowedChars {
+        /// Characters that the generator draws from.
+        alphabet: &'static str,
+    },
+    /// The generator no longer produces valid IDs, e.g. because sequential IDs
+    /// no longer fit in the maximum length, or 100 consecutive IDs were
+    /// rejected. Holds the last ID's error.
+    Exhausted(E),
+}
+
+impl<E> fmt::Display for IdGeneratorError<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidId(error) => write!(f, "The generator would produce invalid IDs: {error}"),
+            Self::NoAllowedChars { alphabet } => write!(
+                f,
+                "The generator draws from `{alphabet}`, but the ID type does not allow them."
+            ),
+            Self::Exhausted(error) => write!(f, "The generator is exhausted: {error}"),
+        }
+    }
+}
+
+impl<E> Error for IdGeneratorError<E>
+where
+    E: Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidId(error) | Self::Exhausted(error) => Some(error),
+            Self::NoAllowedChars { .. } => None,
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::{IdRules, Interner};
+
+/// Marker for a kind of [`Id`], which supplies its type name and rules.
+///
+/// Kinds are usually declared with `id_kind!`, which declares an uninhabited
+/// enum and implements this trait:
+///
+/// ```rust
+/// use id_newtype::{Id, IdKind};
+///
+/// id_newtype::id_kind!(pub User);
+/// id_newtype::id_kind!(pub K8sName; first = "a-z", rest = "a-z0-9-");
+///
+/// let user_id = Id::<User>::new("alice").unwrap();
+/// let k8s_name = Id::<K8sName>::new("web-server").unwrap();
+///
+/// assert_eq!("User", User::TYPE_NAME);
+/// assert_eq!("alice", user_id.as_str());
+/// assert!(Id::<K8sName>::new("Web_Server").is_err());
+/// ```
+///
+/// Kinds may also implement this trait directly:
+///
+/// ```rust
+/// use id_newtype::{Id, IdKind, IdRules};
+///
+/// pub enum Order {}
+///
+/// impl IdKind for Order {
+///     const RULES: IdRules = IdRules::new().max_len(16);
+///     const TYPE_NAME: &'static str = "Order";
+/// }
+///
+/// assert!(Id::<Order>::new("order_0001").is_ok());
+/// ```
+///
+/// [`Id`]: crate::Id
+pub trait IdKind: 'static {
+    /// Name of the ID type, used in error messages, e.g. `"User"`.
+    const TYPE_NAME: &'static str;
+
+    /// Rules that a valid ID of this kind must satisfy.
+    ///
+    /// Predicates are not supported, as IDs are validated in `const`
+    /// functions.
+    const RULES: IdRules = IdRules::DEFAULT;
+
+    /// Returns the interner for IDs of this kind.
+    fn interner() -> &'static Interner {
+        Interner::for_type::<Self>()
+    }
+}
@@ -0,0 +1,434 @@
+use std::{borrow::Borrow, error::Error, fmt, ops::RangeBounds};
+
+use crate::{CaseDisplay, CaseStyle, IdEdit, IdRules, IdSet, IdViolation, UniquifyError, Words};
+
+/// Behaviour shared by every ID type declared with `id_newtype!`.
+///
+/// This is implemented by `id_newtype!` and `#[derive(IdNewtype)]` for each ID
+/// type, so that generic code can validate, parse, and read IDs of any type.
+///
+/// The trait's methods have the same names as the ID types' inherent methods,
+/// which are used when the type is known.
+///
+/// ```rust
+/// use std::borrow::Cow;
+///
+/// use id_newtype::IdNewtype;
+///
+/// #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// pub struct MyId(Cow<'static, str>);
+///
+/// id_newtype::id_newtype!(MyId, MyIdInvalidFmt);
+///
+/// fn load<T: IdNewtype>(s: &str) -> Result<T, T::Error> {
+///     T::try_from_str(s.trim())
+/// }
+///
+/// let my_id = load::<MyId>(" web ").unwrap();
+///
+/// assert_eq!("web", my_id.as_str());
+/// assert_eq!("MyId", MyId::TYPE_NAME);
+/// assert!(load::<MyId>("a b").is_err());
+/// ```
+pub trait IdNewtype: AsRef<str> + Borrow<str> + fmt::Display + Sized {
+    /// Name of the ID type, e.g. `"MyId"`.
+    const TYPE_NAME: &'static str;
+
+    /// Rules that a valid ID must satisfy.
+    const RULES: IdRules;
+
+    /// Regex that matches valid IDs, as described by [`IdRules::pattern`].
+    const PATTERN: &'static str;
+
+    /// Error returned when a value is not a valid ID.
+    type Error: Error + Send + Sync + 'static;
+
+    /// Type of the value held by the ID, e.g. `Cow<'static, str>`.
+    type Inner;
+
+    /// Returns whether the provided `&str` is a valid ID.
+    fn is_valid_id(proposed_id: &str) -> bool {
+        Self::validate(proposed_id).is_ok()
+    }
+
+    /// Returns the first violation of this type's rules in the provided
+    /// `&str`, if any.
+    ///
+    /// Unlike [`IdRules::validate`], this also calls the predicate, if any.
+    fn validate(proposed_id: &str) -> Result<(), IdViolation>;
+
+    /// Returns an ID holding a copy of the provided `&str`, if it is valid.
+    ///
+    /// This is the same as the ID type's `FromStr` implementation.
+    fn try_from_str(s: &str) -> Result<Self, Self::Error>;
+
+    /// Returns an ID holding the provided `String`, if it is valid.
+    ///
+    /// ID types implement this with their `TryFrom<String>` implementation,
+    /// which does not copy the string.
+    fn try_from_string(s: String) -> Result<Self, Self::Error> {
+        Self::try_from_str(&s)
+    }
+
+    /// Returns the `&str` held by this ID.
+    fn as_str(&self) -> &str;
+
+    /// Returns the value held by this ID.
+    fn into_inner(self) -> Self::Inner;
+
+    /// Returns a guard to edit the value of this ID as a `String`, which is
+    /// validated when it is committed.
+    ///
+    /// ID types do not implement `DerefMut`, as changing the value directly
+    /// could make the ID invalid. See [`IdEdit`] for details.
+    fn edit(&mut self) -> IdEdit<'_, Self> {
+        IdEdit::new(self)
+    }
+
+    /// Appends a separator and the given segment to this ID, e.g. `web` and
+    /// `server` become `web_server`.
+    ///
+    /// The separator is `_`, or `-` if `_` is not allowed, or nothing if
+    /// neither is allowed. The segment may be another ID, of any type.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, leaving this ID unchanged, if the result is not
+    /// valid, e.g. because it is too long.
+    ///
+    /// ```rust
+    /// use std::borrow::Cow;
+    ///
+    /// use id_newtype::IdNewtype;
+    ///
+    /// #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    /// pub struct MyId(Cow<'static, str>);
+    ///
+    /// id_newtype::id_newtype!(MyId, MyIdInvalidFmt);
+    ///
+    /// let mut my_id = MyId::new("web").unwrap();
+    /// my_id.push_segment(&MyId::new("server").unwrap()).unwrap();
+    /// assert_eq!("web_server", my_id.as_str());
+    ///
+    /// my_id.replace_range(..3, "app").unwrap();
+    /// assert_eq!("app_server", my_id.as_str());
+    ///
+    /// assert!(my_id.replace_range(..3, "a p").is_err());
+    /// assert_eq!("app_server", my_id.as_str());
+    ///
+    /// assert_eq!("my_app_server", my_id.with_prefix("my_").unwrap().as_str());
+    /// assert_eq!("app_server_2", my_id.with_suffix("_2").unwrap().as_str());
+    /// ```
+    fn push_segment<S>(&mut self, segment: &S) -> Result<(), Self::Error>
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        let mut edit = self.edit();
+        edit.push_str(separator(&Self::RULES));
+        edit.push_str(segment.as_ref());
+        edit.commit()
+    }
+
+    /// Returns a copy of this ID with the given prefix, if it is valid.
+    fn with_prefix(&self, prefix: &str) -> Result<Self, Self::Error> {
+        let mut value = String::with_capacity(prefix.len() + self.as_str().len());
+        value.push_str(prefix);
+        value.push_str(self.as_str());
+        Self::try_from_string(value)
+    }
+
+    /// Returns a copy of this ID with the given suffix, if it is valid.
+    fn with_suffix(&self, suffix: &str) -> Result<Self, Self::Error> {
+        let mut value = String::with_capacity(self.as_str().len() + suffix.len());
+        value.push_str(self.as_str());
+        value.push_str(suffix);
+        Self::try_from_string(value)
+    }
+
+    /// Replaces the given byte range of this ID with the given `&str`, like
+    /// [`String::replace_range`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, leaving this ID unchanged, if the result is not
+    /// valid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, or does not lie on `char`
+    /// boundaries.
+    fn replace_range<R>(&mut self, range: R, replace_with: &str) -> Result<(), Self::Error>
+    where
+        R: RangeBounds<usize>,
+    {
+        let mut edit = self.edit();
+        edit.replace_range(range, replace_with);
+        edit.commit()
+    }
+
+    /// Returns an iterator over the words of this ID.
+    ///
+    /// See [`Words`] for how IDs are split into words.
+    fn words(&self) -> Words<'_> {
+        Words::new(self.as_str())
+    }
+
+    /// Returns a value that displays this ID in the given case style.
+    ///
+    /// Unlike the `to_*_case` methods, this works for any ID type, e.g. to
+    /// render IDs as type names in generated code.
+    fn display_case(&self, style: CaseStyle) -> CaseDisplay<'_> {
+        CaseDisplay::new(self.as_str(), style)
+    }
+
+    /// Returns this ID in `snake_case`.
+    ///
+    /// This is only available for ID types that accept every case style,
+    /// i.e. those with the default rules and no reserved words or length
+    /// limits. Other types fail to compile, and may use [`display_case`].
+    ///
+    /// ```rust
+    /// use std::borrow::Cow;
+    ///
+    /// use id_newtype::{CaseStyle, IdNewtype};
+    ///
+    /// #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    /// pub struct MyId(Cow<'static, str>);
+    ///
+    /// id_newtype::id_newtype!(MyId, MyIdInvalidFmt);
+    ///
+    /// let my_id = MyId::from_camel_case("webServer").unwrap();
+    ///
+    /// assert_eq!("web_server", my_id.as_str());
+    /// assert_eq!("WebServer", my_id.to_pascal_case().as_str());
+    /// assert_eq!("WEB_SERVER", my_id.to_screaming_snake_case().as_str());
+    /// assert_eq!(vec!["web", "server"], my_id.words().collect::<Vec<_>>());
+    /// assert_eq!(
+    ///     "struct WebServer;",
+    ///     format!("struct {};", my_id.display_case(CaseStyle::Pascal))
+    /// );
+    /// ```
+    ///
+    /// [`display_case`]: Self::display_case
+    fn to_snake_case(&self) -> Self {
+        to_case(self, CaseStyle::Snake)
+    }
+
+    /// Returns this ID in `camelCase`.
+    ///
+    /// See [`to_snake_case`] for which ID types this is available for.
+    ///
+    /// [`to_snake_case`]: Self::to_snake_case
+    fn to_camel_case(&self) -> Self {
+        to_case(self, CaseStyle::Camel)
+    }
+
+    /// Returns this ID in `PascalCase`.
+    ///
+    /// See [`to_snake_case`] for which ID types this is available for.
+    ///
+    /// [`to_snake_case`]: Self::to_snake_case
+    fn to_pascal_case(&self) -> Self {
+        to_case(self, CaseStyle::Pascal)
+    }
+
+    /// Returns this ID in `SCREAMING_SNAKE_CASE`.
+    ///
+    /// See [`to_snake_case`] for which ID types this is available for.
+    ///
+    /// [`to_snake_case`]: Self::to_snake_case
+    fn to_screaming_snake_case(&self) -> Self {
+        to_case(self, CaseStyle::ScreamingSnake)
+    }
+
+    /// Returns an ID in `snake_case` from `camelCase` text, if it is valid.
+    fn from_camel_case(s: &str) -> Result<Self, Self::Error> {
+        from_case(s)
+    }
+
+    /// Returns an ID in `snake_case` from `PascalCase` text, if it is valid.
+    fn from_pascal_case(s: &str) -> Result<Self, Self::Error> {
+        from_case(s)
+    }
+
+    /// Returns an ID in `snake_case` from `SCREAMING_SNAKE_CASE` text, if it is
+    /// valid.
+    fn from_screaming_snake_case(s: &str) -> Result<Self, Self::Error> {
+        from_case(s)
+    }
+
+    /// Returns the base of this ID and its trailing index, if any.
+    ///
+    /// The index is a number without leading zeros after the index separator,
+    /// which is `_`, or `-` if `_` is not allowed, or nothing if neither is
+    /// allowed. For example, `web_3` is split into `("web", Some(3))`, and
+    /// `web_03` is not split.
+    fn split_index(&self) -> (&str, Option<u64>) {
+        split_index(self.as_str(), separator(&Self::RULES))
+    }
+
+    /// Returns this ID if it is not taken, otherwise the first variant with a
+    /// numeric index that is not taken, e.g. `web_2`, `web_3`.
+    ///
+    /// If this ID already has an index, the variants continue from it, so
+    /// `web_2` becomes `web_3`. The base is truncated so that variants fit in
+    /// the maximum length, if any, and trailing separators are removed from
+    /// it. Variants that are not valid are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UniquifyError::InvalidId`] with the first free variant's
+    /// error if none of the first 10000 variants are both free and valid, e.g.
+    /// because the index characters are not allowed, or the variants have a
+    /// reserved prefix.
+    ///
+    /// Returns [`UniquifyError::Exhausted`] if the first 10000 variants are
+    /// all taken, or the index would overflow a `u64`.
+    ///
+    /// ```rust
+    /// use std::{borrow::Cow, collections::HashSet};
+    ///
+    /// use id_newtype::IdNewtype;
+    ///
+    /// #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    /// pub struct MyId(Cow<'static, str>);
+    ///
+    /// id_newtype::id_newtype!(MyId, MyIdInvalidFmt);
+    ///
+    /// let taken = HashSet::from([MyId::new("web").unwrap(), MyId::new("web_2").unwrap()]);
+    /// let web = MyId::new("web").unwrap();
+    ///
+    /// assert_eq!("web_3", web.uniquify_in(&taken).unwrap().as_str());
+    /// assert_eq!("web", web.uniquify(|_| false).unwrap().as_str());
+    /// assert_eq!(("web", Some(3)), MyId::new("web_3").unwrap().split_index());
+    /// ```
+    fn uniquify<F>(&self, mut is_taken: F) -> Result<Self, UniquifyError<Self::Error>>
+    where
+        Self: Clone,
+        F: FnMut(&str) -> bool,
+    {
+        if !is_taken(self.as_str()) {
+            return Ok(self.clone());
+        }
+
+        let separator = separator(&Self::RULES);
+        let (base, index) = split_index(self.as_str(), separator);
+        let mut index = match index {
+            Some(index) => index.checked_add(1).map(|index| index.max(2)),
+            None => Some(2),
+        };
+        let mut candidate = String::with_capacity(self.as_str().len() + 4);
+        let mut attempts = 0;
+        let mut first_error = None;
+        while let Some(index_current) = index
+            && attempts < UNIQUIFY_MAX_ATTEMPTS
+        {
+            let suffix = format!("{separator}{index_current}");
+            let base_len = Self::RULES.max_len_limit().map_or(base.len(), |max_len| {
+                let base_len = max_len.saturating_sub(suffix.len()).min(base.len());
+                (0..=base_len)
+                    .rev()
+                    .find(|base_len| base.is_char_boundary(*base_len))
+                    .unwrap_or(0)
+            });
+
+            candidate.clear();
+            candidate.push_str(base[..base_len].trim_end_matches(separator));
+            candidate.push_str(&suffix);
+            if !is_taken(&candidate) {
+                match Self::try_from_str(&candidate) {
+                    Ok(id) => return Ok(id),
+                    Err(error) => {
+                        first_error.get_or_insert(error);
+                    }
+                }
+            }
+            attempts += 1;
+            index = index_current.checked_add(1);
+        }
+
+        match first_error {
+            Some(error) => Err(UniquifyError::InvalidId(error)),
+            None => Err(UniquifyError::Exhausted {
+                value: String::from(self.as_str()),
+                attempts,
+            }),
+        }
+    }
+
+    /// Returns this ID if it is not in `taken`, otherwise the first variant
+    /// with a numeric index that is not in `taken`.
+    ///
+    /// See [`uniquify`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the free variant is not valid, or no free variant
+    /// is found.
+    ///
+    /// [`uniquify`]: Self::uniquify
+    fn uniquify_in<S>(&self, taken: &S) -> Result<Self, UniquifyError<Self::Error>>
+    where
+        Self: Clone,
+        S: IdSet + ?Sized,
+    {
+        self.uniquify(|candidate| taken.contains_id(candidate))
+    }
+}
+
+/// Number of variants that `IdNewtype::uniquify` tries before it returns
+/// `UniquifyError::Exhausted`.
+const UNIQUIFY_MAX_ATTEMPTS: usize = 10_000;
+
+/// Returns an ID in `snake_case` from text in any case style, if it is valid.
+fn from_case<T>(s: &str) -> Result<T, T::Error>
+where
+    T: IdNewtype,
+{
+    T::try_from_str(&CaseStyle::Snake.convert(s))
+}
+
+/// Returns the ID in the given case style, which fails to compile if the ID
+/// type does not accept every case style.
+fn to_case<T>(id: &T, style: CaseStyle) -> T
+where
+    T: IdNewtype,
+{
+    const {
+        assert!(
+            T::RULES.accepts_case_styles(),
+            "Case conversions are only available for ID types that accept every case style. \
+            Use `display_case` instead."
+        );
+    }
+
+    T::try_from_str(&style.convert(id.as_str()))
+        .unwrap_or_else(|error| unreachable!("Case styles of IDs are valid: {error}"))
+}
+
+/// Returns the separator between the segments of an ID, and between its base
+/// and its index.
+fn separator(rules: &IdRules) -> &'static str {
+    let rest = rules.rest_class();
+    if rest.contains('_') {
+        "_"
+    } else if rest.contains('-') {
+        "-"
+    } else {
+        ""
+    }
+}
+
+/// Returns the base of the ID and its trailing index, if any.
+fn split_index<'id>(id: &'id str, separator: &str) -> (&'id str, Option<u64>) {
+    let (rest, digits) = id.split_at(id.trim_end_matches(|c: char| c.is_ascii_digit()).len());
+    match rest.strip_suffix(separator) {
+        Some(base) if !base.is_empty() && !digits.is_empty() && !digits.starts_with('0') => {
+            match digits.parse() {
+                Ok(index) => (base, Some(index)),
+                Err(_) => (id, None),
+            }
+        }
+        _ => (id, None),
+    }
+}
@@ -0,0 +1,42 @@
+use std::fmt;
+
+use crate::PackedId;
+
+/// Error packing an ID into a [`PackedId`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IdPackError {
+    /// The ID is longer than [`PackedId::MAX_LEN`] characters.
+    TooLong {
+        /// Number of characters in the ID.
+        len: usize,
+    },
+    /// The ID contains a character outside `[A-Za-z0-9_]`.
+    InvalidChar {
+        /// The character that cannot be packed.
+        invalid_char: char,
+        /// Byte offset of the character in the ID.
+        offset: usize,
+    },
+}
+
+impl fmt::Display for IdPackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooLong { len } => write!(
+                f,
+                "The ID is {len} characters long, but at most {} characters can be packed.",
+                PackedId::<()>::MAX_LEN
+            ),
+            Self::InvalidChar {
+                invalid_char,
+                offset,
+            } => write!(
+                f,
+                "`{invalid_char}` at offset {offset} cannot be packed, \
+                only `[A-Za-z0-9_]` characters can be packed."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for IdPackError {}
@@ -0,0 +1,247 @@
+use std::{fmt, str::FromStr};
+
+use crate::{IdNewtype, IdPathError};
+
+/// Hierarchical ID made of segments of an ID type, e.g. `region.cluster.node`.
+///
+/// The separator defaults to `.`, and may be changed with the second type
+/// parameter, e.g. `IdPath<MyId, '/'>`. Each segment is validated with the
+/// segment type's rules, which must not allow the separator.
+///
+/// Paths always have at least one segment.
+///
+/// ```rust
+/// use std::borrow::Cow;
+///
+/// use id_newtype::IdPath;
+///
+/// #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// pub struct K8sName(Cow<'static, str>);
+///
+/// id_newtype::id_newtype!(K8sName, K8sNameInvalidFmt; first = "a-z", rest = "a-z0-9-");
+///
+/// let node: IdPath<K8sName> = "us-east.cluster-1.node-1".parse().unwrap();
+/// let cluster = node.parent().unwrap();
+///
+/// assert_eq!("us-east.cluster-1", cluster.to_string());
+/// assert!(node.starts_with(&cluster));
+/// assert_eq!(node, cluster.join(K8sName::new("node-1").unwrap()));
+/// assert_eq!(
+///     vec!["us-east", "cluster-1", "node-1"],
+///     node.segments().map(K8sName::as_str).collect::<Vec<_>>()
+/// );
+///
+/// assert!("us-east..node-1".parse::<IdPath<K8sName>>().is_err());
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct IdPath<T, const SEPARATOR: char = '.'> {
+    /// Segments of the path, of which there is at least one.
+    segments: Vec<T>,
+}
+
+impl<T, const SEPARATOR: char> IdPath<T, SEPARATOR> {
+    /// Returns the path without its last segment, or `None` if it has one
+    /// segment.
+    pub fn parent(&self) -> Option<Self>
+    where
+        T: Clone,
+    {
+        match self.segments.split_last() {
+            Some((_, [])) | None => None,
+            Some((_, parent)) => Some(Self {
+                segments: parent.to_vec(),
+            }),
+        }
+    }
+
+    /// Returns this path with the given segment appended.
+    pub fn join(&self, segment: T) -> Self
+    where
+        T: Clone,
+    {
+        let mut segments = Vec::with_capacity(self.segments.len() + 1);
+        segments.extend_from_slice(&self.segments);
+        segments.push(segment);
+        Self { segments }
+    }
+
+    /// Returns an iterator over the segments of this path.
+    pub fn segments(&self) -> std::slice::Iter<'_, T> {
+        self.segments.iter()
+    }
+
+    /// Returns the last segment of this path.
+    pub fn last(&self) -> &T {
+        self.segments
+            .last()
+            .expect("Paths always have at least one segment.")
+    }
+
+    /// Returns whether `prefix` is this path or one of its ancestors.
+    ///
+    /// Only whole segments are compared, so `a.bc` does not start with `a.b`.
+    pub fn starts_with(&self, prefix: &Self) -> bool
+    where
+        T: PartialEq,
+    {
+        self.segments.starts_with(&prefix.segments)
+    }
+}
+
+impl<T, const SEPARATOR: char> IdPath<T, SEPARATOR>
+where
+    T: IdNewtype,
+{
+    /// Fails to compile if the segment type allows the separator, as paths
+    /// could then not be parsed unambiguously.
+    const SEPARATOR_IS_NOT_ALLOWED: () = assert!(
+        !T::RULES.first_class().contains(SEPARATOR) && !T::RULES.rest_class().contains(SEPARATOR),
+        "The `IdPath` separator must not be allowed in the segment type."
+    );
+
+    /// Returns a new `IdPath` if each segment of the given `&str` is valid.
+    pub fn new(s: &str) -> Result<Self, IdPathError<T::Error>> {
+        let () = Self::SEPARATOR_IS_NOT_ALLOWED;
+
+        s.split(SEPARATOR)
+            .enumerate()
+            .map(|(index, segment)| {
+                T::try_from_str(segment)
+                    .map_err(|error| IdPathError::new(String::from(s), index, error))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(|segments| Self { segments })
+    }
+
+    /// Returns a new `IdPath` from segments without verifying that there is
+    /// at least one.
+    ///
+    /// This is here for guaranteed valid usage such as being called from the
+    /// `id_path!` macro.
+    #[doc(hidden)]
+    pub fn new_unchecked(segments: Vec<T>) -> Self {
+        let () = Self::SEPARATOR_IS_NOT_ALLOWED;
+
+        Self { segments }
+    }
+}
+
+impl<T, const SEPARATOR: char> From<T> for IdPath<T, SEPARATOR>
+where
+    T: IdNewtype,
+{
+    fn from(segment: T) -> Self {
+        Self::new_unchecked(vec![segment])
+    }
+}
+
+impl<T, const SEPARATOR: char> FromStr for IdPath<T, SEPARATOR>
+where
+    T: IdNewtype,
+{
+    type Err = IdPathError<T::Error>;
+
+    fn from_str(s: &str) -> Result<Self, IdPathError<T::Error>> {
+        Self::new(s)
+    }
+}
+
+impl<T, const SEPARATOR: char> fmt::Display for IdPath<T, SEPARATOR>
+where
+    T: AsRef<str>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.segments
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, segment)| {
+                if index > 0 {
+                    write!(f, "{SEPARATOR}")?;
+                }
+                f.write_str(segment.as_ref())
+            })
+    }
+}
+
+impl<T, const SEPARATOR: char> fmt::Debug for IdPath<T, SEPARATOR>
+where
+    T: AsRef<str>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IdPath").field(&self.to_string()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::IdPath;
+    use crate::InvalidReason;
+
+    #[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct K8sName(Cow<'static, str>);
+
+    crate::id_newtype!(K8sName, K8sNameInvalidFmt; first = "a-z", rest = "a-z0-9-");
+
+    fn path(s: &str) -> IdPath<K8sName> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let path = path("us-east.cluster-1.node-1");
+
+        assert_eq!(3, path.segments().len());
+        assert_eq!("node-1", path.last().as_str());
+        assert_eq!("us-east.cluster-1.node-1", path.to_string());
+        assert_eq!(r#"IdPath("us-east.cluster-1.node-1")"#, format!("{path:?}"));
+    }
+
+    #[test]
+    fn parse_with_separator() {
+        let path = "us-east/cluster-1".parse::<IdPath<K8sName, '/'>>().unwrap();
+
+        assert_eq!("us-east/cluster-1", path.to_string());
+        assert!("us-east.cluster-1".parse::<IdPath<K8sName, '/'>>().is_err());
+    }
+
+    #[test]
+    fn parse_invalid_segment() {
+        let error = "us-east..node-1".parse::<IdPath<K8sName>>().unwrap_err();
+
+        assert_eq!(1, error.index());
+        assert_eq!("us-east..node-1", error.path());
+        assert_eq!(InvalidReason::Empty, error.error().reason());
+
+        let error = "us-east.Cluster".parse::<IdPath<K8sName>>().unwrap_err();
+        assert_eq!(
+            "`us-east.Cluster` has an invalid segment at index 1: \
+            `Cluster` is not a valid `K8sName`: invalid first character `C` at column 1.",
+            error.to_string().lines().next().unwrap()
+        );
+        assert!("".parse::<IdPath<K8sName>>().is_err());
+    }
+
+    #[test]
+    fn parent_and_join() {
+        let node = path("us-east.cluster-1.node-1");
+        let cluster = node.parent().unwrap();
+        let region = cluster.parent().unwrap();
+
+        assert_eq!(path("us-east.cluster-1"), cluster);
+        assert_eq!(None, region.parent());
+        assert_eq!(node, cluster.join(K8sName::new("node-1").unwrap()));
+        assert_eq!(region, IdPath::from(K8sName::new("us-east").unwrap()));
+    }
+
+    #[test]
+    fn starts_with_compares_whole_segments() {
+        let node = path("us-east.cluster-1.node-1");
+
+        assert!(node.starts_with(&path("us-east")));
+        assert!(node.starts_with(&node));
+        assert!(!node.starts_with(&path("us-east.cluster")));
+        assert!(!path("us-east").starts_with(&node));
+    }
+}
@@ -0,0 +1,63 @@
+use std::{error::Error, fmt};
+
+/// Error parsing an [`IdPath`], where `E` is the segment type's error.
+///
+/// [`IdPath`]: crate::IdPath
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IdPathError<E> {
+    /// The path that was provided.
+    path: String,
+    /// Index of the invalid segment.
+    index: usize,
+    /// Error for the invalid segment.
+    error: E,
+}
+
+impl<E> IdPathError<E> {
+    /// Returns a new `IdPathError`.
+    pub fn new(path: String, index: usize, error: E) -> Self {
+        Self { path, index, error }
+    }
+
+    /// Returns the path that was provided.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the index of the invalid segment.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the error for the invalid segment.
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    /// Returns the error for the invalid segment, consuming this error.
+    pub fn into_error(self) -> E {
+        self.error
+    }
+}
+
+impl<E> fmt::Display for IdPathError<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` has an invalid segment at index {}: {}",
+            self.path, self.index, self.error
+        )
+    }
+}
+
+impl<E> Error for IdPathError<E>
+where
+    E: Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
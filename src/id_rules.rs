@@ -0,0 +1,1223 @@
+use std::fmt;
+
+use crate::{
+    const_str, transliterate::transliterate, CaseStyle, CharClass, IdDecodeError, IdViolation,
+    InvalidReason, ReservedSet,
+};
+
+/// Rules that a string must satisfy to be a valid ID.
+///
+/// Each type declared with `id_newtype!` has an associated `RULES` constant,
+/// built from the options passed to the macro.
+///
+/// # Examples
+///
+/// ```rust
+/// use id_newtype::IdRules;
+///
+/// const K8S_NAME: IdRules = IdRules::new().first("a-z").rest("a-z0-9-");
+///
+/// assert!(K8S_NAME.is_valid_id("web-server-2"));
+/// assert!(!K8S_NAME.is_valid_id("Web_Server"));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IdRules {
+    /// Characters that an ID may begin with.
+    first: CharClass,
+    /// Characters that an ID may contain after the first character.
+    rest: CharClass,
+    /// Minimum length of an ID in bytes.
+    min_len: Option<usize>,
+    /// Maximum length of an ID in bytes.
+    max_len: Option<usize>,
+    /// Words that an ID must not be.
+    reserved: &'static [&'static str],
+    /// Prefixes that an ID must not begin with.
+    reserved_prefixes: &'static [&'static str],
+    /// Built-in sets of words that an ID must not be.
+    reserved_sets: &'static [ReservedSet],
+    /// Case style that an ID must be written in.
+    style: Option<CaseStyle>,
+    /// Name of the predicate that IDs must additionally satisfy.
+    predicate_name: Option<&'static str>,
+}
+
+impl IdRules {
+    /// The default rules: `[A-Za-z_][A-Za-z0-9_]*`.
+    pub const DEFAULT: Self = Self {
+        first: CharClass::new("A-Za-z_"),
+        rest: CharClass::new("A-Za-z0-9_"),
+        min_len: None,
+        max_len: None,
+        reserved: &[],
+        reserved_prefixes: &[],
+        reserved_sets: &[],
+        style: None,
+        predicate_name: None,
+    };
+    /// Maximum length of a pattern in bytes.
+    ///
+    /// Each character class is at most 172 bytes, and the repetition is at
+    /// most 43 bytes.
+    pub(crate) const PATTERN_MAX_LEN: usize = 389;
+
+    /// Returns the default rules: `[A-Za-z_][A-Za-z0-9_]*`.
+    pub const fn new() -> Self {
+        Self::DEFAULT
+    }
+
+    /// Sets the characters that an ID may begin with.
+    ///
+    /// See [`CharClass`] for the specification format.
+    pub const fn first(mut self, spec: &'static str) -> Self {
+        self.first = CharClass::new(spec);
+        self
+    }
+
+    /// Sets the characters that an ID may contain after the first character.
+    ///
+    /// See [`CharClass`] for the specification format.
+    pub const fn rest(mut self, spec: &'static str) -> Self {
+        self.rest = CharClass::new(spec);
+        self
+    }
+
+    /// Sets the minimum length of an ID in bytes.
+    ///
+    /// As character classes only contain ASCII characters, this is also the
+    /// number of characters.
+    pub const fn min_len(mut self, min_len: usize) -> Self {
+        self.min_len = Some(min_len);
+        self
+    }
+
+    /// Sets the maximum length of an ID in bytes.
+    ///
+    /// As character classes only contain ASCII characters, this is also the
+    /// number of characters.
+    pub const fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    /// Sets the words that an ID must not be, compared case sensitively.
+    pub const fn reserved(mut self, reserved: &'static [&'static str]) -> Self {
+        self.reserved = reserved;
+        self
+    }
+
+    /// Sets the prefixes that an ID must not begin with, e.g. `"__"`.
+    pub const fn reserved_prefixes(mut self, reserved_prefixes: &'static [&'static str]) -> Self {
+        self.reserved_prefixes = reserved_prefixes;
+        self
+    }
+
+    /// Sets the built-in sets of words that an ID must not be.
+    pub const fn reserved_sets(mut self, reserved_sets: &'static [ReservedSet]) -> Self {
+        self.reserved_sets = reserved_sets;
+        self
+    }
+
+    /// Sets the [`CaseStyle`] that an ID must be written in.
+    ///
+    /// This also sets the character classes for the style, which may be
+    /// changed afterwards with [`IdRules::first`] and [`IdRules::rest`]:
+    ///
+    /// * `CaseStyle::Snake`: `[a-z][a-z0-9_]*`, without repeated or trailing
+    ///   underscores.
+    /// * `CaseStyle::ScreamingSnake`: `[A-Z][A-Z0-9_]*`, without repeated or
+    ///   trailing underscores.
+    /// * `CaseStyle::Camel`: `[a-z][A-Za-z0-9]*`.
+    /// * `CaseStyle::Pascal`: `[A-Z][A-Za-z0-9]*`.
+    ///
+    /// Letters in the wrong case, and repeated or trailing underscores, are
+    /// reported with their own [`InvalidReason`]s.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use id_newtype::{CaseStyle, IdRules, InvalidReason};
+    ///
+    /// const RULES: IdRules = IdRules::new().style(CaseStyle::Snake);
+    ///
+    /// assert!(RULES.is_valid_id("web_server_2"));
+    /// assert_eq!(
+    ///     Err(InvalidReason::UppercaseLetter),
+    ///     RULES
+    ///         .validate("WeB__x_")
+    ///         .map_err(|violation| violation.reason())
+    /// );
+    /// assert_eq!(
+    ///     Err(InvalidReason::RepeatedUnderscore),
+    ///     RULES
+    ///         .validate("web__x_")
+    ///         .map_err(|violation| violation.reason())
+    /// );
+    /// ```
+    pub const fn style(mut self, style: CaseStyle) -> Self {
+        self.style = Some(style);
+        self.first = CharClass::new(style.first_spec());
+        self.rest = CharClass::new(style.rest_spec());
+        self
+    }
+
+    /// Sets the name of the predicate that IDs must additionally satisfy.
+    ///
+    /// This is only used to describe the rules, as the predicate itself is
+    /// called by the code generated by `id_newtype!`.
+    #[doc(hidden)]
+    pub const fn with_predicate_name(mut self, predicate_name: &'static str) -> Self {
+        self.predicate_name = Some(predicate_name);
+        self
+    }
+
+    /// Returns the characters that an ID may begin with.
+    pub const fn first_class(&self) -> &CharClass {
+        &self.first
+    }
+
+    /// Returns the characters that an ID may contain after the first
+    /// character.
+    pub const fn rest_class(&self) -> &CharClass {
+        &self.rest
+    }
+
+    /// Returns the minimum length of an ID in bytes, if any.
+    pub const fn min_len_limit(&self) -> Option<usize> {
+        self.min_len
+    }
+
+    /// Returns the maximum length of an ID in bytes, if any.
+    pub const fn max_len_limit(&self) -> Option<usize> {
+        self.max_len
+    }
+
+    /// Returns the words that an ID must not be.
+    pub const fn reserved_words(&self) -> &'static [&'static str] {
+        self.reserved
+    }
+
+    /// Returns the prefixes that an ID must not begin with.
+    pub const fn reserved_prefix_list(&self) -> &'static [&'static str] {
+        self.reserved_prefixes
+    }
+
+    /// Returns the built-in sets of words that an ID must not be.
+    pub const fn reserved_set_list(&self) -> &'static [ReservedSet] {
+        self.reserved_sets
+    }
+
+    /// Returns the case style that an ID must be written in, if any.
+    pub const fn case_style(&self) -> Option<CaseStyle> {
+        self.style
+    }
+
+    /// Returns the name of the predicate that IDs must additionally satisfy.
+    pub const fn predicate_name(&self) -> Option<&'static str> {
+        self.predicate_name
+    }
+
+    /// Returns whether every value matching `[A-Za-z_][A-Za-z0-9_]*` is
+    /// valid, so IDs may be converted to any [`CaseStyle`] without failing.
+    ///
+    /// [`CaseStyle`]: crate::CaseStyle
+    pub(crate) const fn accepts_case_styles(&self) -> bool {
+        let mut c = b'0';
+        while c <= b'z' {
+            let is_letter = c.is_ascii_alphabetic() || c == b'_';
+            if (is_letter && !self.first.contains(c as char))
+                || ((is_letter || c.is_ascii_digit()) && !self.rest.contains(c as char))
+            {
+                return false;
+            }
+            c += 1;
+        }
+
+        matches!(self.min_len, None | Some(0..=1))
+            && self.max_len.is_none()
+            && self.reserved.is_empty()
+            && self.reserved_prefixes.is_empty()
+            && self.reserved_sets.is_empty()
+            && self.style.is_none()
+            && self.predicate_name.is_none()
+    }
+
+    /// Returns a regex that matches values satisfying the length and character
+    /// rules, e.g. `^[A-Za-z_][A-Za-z0-9_]*$`.
+    ///
+    /// The pattern is valid as an ECMAScript regex, as used by JSON Schema and
+    /// OpenAPI, and as a Rust regex. Reserved words, reserved prefixes, the
+    /// case style's underscore rules, and the predicate, if any, are not
+    /// expressed in the pattern.
+    ///
+    /// Types declared with `id_newtype!` have the same pattern in their
+    /// `PATTERN` constant.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use id_newtype::IdRules;
+    ///
+    /// assert_eq!("^[A-Za-z_][A-Za-z0-9_]*$", IdRules::new().pattern());
+    ///
+    /// let rules = IdRules::new().first("a-z").rest("a-z0-9-").max_len(63);
+    /// assert_eq!("^[a-z][a-z0-9\\-]{0,62}$", rules.pattern());
+    /// ```
+    pub fn pattern(&self) -> String {
+        let mut bytes = vec![0; self.pattern_len()];
+        self.write_pattern(&mut bytes);
+        String::from_utf8(bytes).expect("Patterns only contain ASCII characters.")
+    }
+
+    /// Returns the length of the pattern in bytes.
+    ///
+    /// This is used with [`IdRules::pattern_bytes`] by the code generated by
+    /// `id_newtype!` to build the pattern in a `const`.
+    #[doc(hidden)]
+    pub const fn pattern_len(&self) -> usize {
+        self.write_pattern(&mut [])
+    }
+
+    /// Returns the bytes of the pattern, where `N` is the pattern's length.
+    #[doc(hidden)]
+    pub const fn pattern_bytes<const N: usize>(&self) -> [u8; N] {
+        let mut bytes = [0; N];
+        self.write_pattern(&mut bytes);
+        bytes
+    }
+
+    /// Writes the pattern into `buf`, and returns its length.
+    const fn write_pattern(&self, buf: &mut [u8]) -> usize {
+        let mut len = const_str::write(buf, 0, b"^");
+        len = self.first.write_pattern(buf, len);
+
+        if !self.rest.is_empty() {
+            let rest_min = match self.min_len {
+                Some(min_len) => min_len.saturating_sub(1),
+                None => 0,
+            };
+            let rest_max = match self.max_len {
+                Some(max_len) => Some(max_len.saturating_sub(1)),
+                None => None,
+            };
+
+            if !matches!(rest_max, Some(0)) {
+                len = self.rest.write_pattern(buf, len);
+            }
+            match (rest_min, rest_max) {
+                (_, Some(0)) => {}
+                (0, None) => len = const_str::write(buf, len, b"*"),
+                (1, None) => len = const_str::write(buf, len, b"+"),
+                (rest_min, None) => {
+                    len = const_str::write(buf, len, b"{");
+                    len = const_str::write_usize(buf, len, rest_min);
+                    len = const_str::write(buf, len, b",}");
+                }
+                (rest_min, Some(rest_max)) => {
+                    len = const_str::write(buf, len, b"{");
+                    len = const_str::write_usize(buf, len, rest_min);
+                    if rest_min != rest_max {
+                        len = const_str::write(buf, len, b",");
+                        len = const_str::write_usize(buf, len, rest_max);
+                    }
+                    len = const_str::write(buf, len, b"}");
+                }
+            }
+        }
+
+        const_str::write(buf, len, b"$")
+    }
+
+    /// Returns whether the provided `&str` satisfies the character rules.
+    ///
+    /// This does not call the predicate, if any.
+    pub const fn is_valid_id(&self, proposed_id: &str) -> bool {
+        self.validate(proposed_id).is_ok()
+    }
+
+    /// Returns the first violation of the length and character rules in the
+    /// provided `&str`, if any.
+    ///
+    /// The length is checked before any characters, so long values are
+    /// rejected without being scanned. This does not call the predicate, if
+    /// any.
+    ///
+    /// This is a `const fn` over the bytes of the value, so it may be used in
+    /// `const` contexts.
+    pub const fn validate(&self, proposed_id: &str) -> Result<(), IdViolation> {
+        let bytes = proposed_id.as_bytes();
+        if bytes.is_empty() {
+            return Err(IdViolation::new(InvalidReason::Empty, 0, None));
+        }
+        if let Some(max_len) = self.max_len
+            && bytes.len() > max_len
+        {
+            let mut offset = max_len;
+            while !proposed_id.is_char_boundary(offset) {
+                offset -= 1;
+            }
+            return Err(IdViolation::new(InvalidReason::TooLong, offset, None));
+        }
+        if let Some(min_len) = self.min_len
+            && bytes.len() < min_len
+        {
+            return Err(IdViolation::new(InvalidReason::TooShort, bytes.len(), None));
+        }
+
+        // Character classes only contain ASCII characters, so a non-ASCII byte is
+        // always the start of an invalid character.
+        if let Some(reason) = self.style_violation(bytes, 0) {
+            return Err(IdViolation::new(reason, 0, Some(bytes[0] as char)));
+        }
+        if !self.first.contains(bytes[0] as char) {
+            return Err(IdViolation::new(
+                InvalidReason::InvalidFirstChar,
+                0,
+                Some(const_str::char_at(proposed_id, 0)),
+            ));
+        }
+        let mut offset = 1;
+        while offset < bytes.len() {
+            if let Some(reason) = self.style_violation(bytes, offset) {
+                return Err(IdViolation::new(
+                    reason,
+                    offset,
+                    Some(bytes[offset] as char),
+                ));
+            }
+            if !self.rest.contains(bytes[offset] as char) {
+                return Err(IdViolation::new(
+                    InvalidReason::InvalidChar,
+                    offset,
+                    Some(const_str::char_at(proposed_id, offset)),
+                ));
+            }
+            offset += 1;
+        }
+
+        if self.is_reserved(proposed_id) {
+            return Err(IdViolation::new(InvalidReason::Reserved, 0, None));
+        }
+        if let Some(reserved_prefix) = self.reserved_prefix(proposed_id) {
+            return Err(IdViolation::new(
+                InvalidReason::ReservedPrefix,
+                reserved_prefix.len(),
+                None,
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Returns a value built from arbitrary text that satisfies the length and
+    /// character rules.
+    ///
+    /// * Common accented Latin letters are transliterated to ASCII, e.g. `é`
+    ///   becomes `e`.
+    /// * Letters are converted to the other case if only that case is allowed.
+    /// * Each run of other characters that are not allowed is replaced with a
+    ///   single `_`, or `-` if `_` is not allowed, or removed if neither is
+    ///   allowed. Runs at the start and end are removed.
+    /// * With a `snake_case` or `SCREAMING_SNAKE_CASE` [style], leading,
+    ///   repeated, and trailing underscores are removed.
+    /// * Reserved prefixes are removed.
+    /// * If the value does not begin with an allowed character, such as a
+    ///   leading digit, it is prefixed with `_`, or the lowest allowed
+    ///   character if `_` is not allowed.
+    /// * The value is truncated to the maximum length, and trailing `_` or `-`
+    ///   separators are removed.
+    /// * The value is padded to the minimum length.
+    /// * Reserved words are suffixed with `_`.
+    ///
+    /// Values that are already valid and do not end with a separator are
+    /// returned unchanged. This does not call the predicate, if any; the
+    /// generated `from_lossy` does.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use id_newtype::IdRules;
+    ///
+    /// let rules = IdRules::new();
+    /// assert_eq!("Web_Server_2", rules.sanitize("Web Server #2"));
+    /// assert_eq!("_3rd_party_API", rules.sanitize("3rd-party API"));
+    /// assert_eq!("Creme_brulee", rules.sanitize("Crème brûlée"));
+    ///
+    /// let rules = IdRules::new().first("a-z").rest("a-z0-9-");
+    /// assert_eq!("web-server-2", rules.sanitize("Web Server #2"));
+    /// ```
+    ///
+    /// [style]: IdRules::style
+    pub fn sanitize(&self, value: &str) -> String {
+        let separator = ['_', '-'].into_iter().find(|c| self.rest.contains(*c));
+        let has_underscore_rules = self.style.is_some_and(|style| style.has_underscore_rules());
+        let filler = if has_underscore_rules {
+            self.rest.chars().find(|c| *c != '_')
+        } else {
+            separator.or_else(|| self.rest.chars().next())
+        };
+
+        let transliterated = value.chars().fold(
+            String::with_capacity(value.len()),
+            |mut transliterated, c| {
+                match transliterate(c) {
+                    Some(ascii) => transliterated.push_str(ascii),
+                    None => transliterated.push(c),
+                }
+                transliterated
+            },
+        );
+
+        let mut sanitized = String::with_capacity(transliterated.len());
+        let mut separator_pending = false;
+        for c in transliterated.chars() {
+            let at_start = sanitized.is_empty();
+            let is_allowed =
+                |c: &char| self.rest.contains(*c) || (at_start && self.first.contains(*c));
+            let c = [c, c.to_ascii_lowercase(), c.to_ascii_uppercase()]
+                .into_iter()
+                .find(is_allowed);
+
+            match c {
+                Some(c) => {
+                    if separator_pending
+                        && !at_start
+                        && let Some(separator) = separator
+                        && c != separator
+                        && !sanitized.ends_with(separator)
+                    {
+                        sanitized.push(separator);
+                    }
+                    separator_pending = false;
+                    sanitized.push(c);
+                }
+                None => separator_pending = true,
+            }
+        }
+
+        if has_underscore_rules {
+            sanitized = sanitized
+                .split('_')
+                .filter(|word| !word.is_empty())
+                .collect::<Vec<_>>()
+                .join("_");
+        }
+
+        while let Some(reserved_prefix) = self.reserved_prefixes.iter().find(|reserved_prefix| {
+            !reserved_prefix.is_empty() && sanitized.starts_with(**reserved_prefix)
+        }) {
+            sanitized.drain(..reserved_prefix.len());
+        }
+
+        if !sanitized.starts_with(|c| self.first.contains(c)) {
+            let candidates = ['_']
+                .into_iter()
+                .chain(self.first.chars())
+                .filter(|c| self.first.contains(*c))
+                .collect::<Vec<_>>();
+            let first_char = candidates
+                .iter()
+                .find(|c| {
+                    !self.reserved_prefixes.iter().any(|reserved_prefix| {
+                        reserved_prefix
+                            .strip_prefix(**c)
+                            .is_some_and(|reserved_prefix_rest| {
+                                sanitized.starts_with(reserved_prefix_rest)
+                            })
+                    })
+                })
+                .or(candidates.first());
+            if let Some(first_char) = first_char.copied() {
+                sanitized.insert(0, first_char);
+            }
+        }
+
+        // Sanitized values only contain ASCII characters, so they can be truncated
+        // at any byte offset.
+        if let Some(max_len) = self.max_len
+            && sanitized.len() > max_len
+        {
+            sanitized.truncate(max_len);
+        }
+        if let Some(separator) = separator {
+            while sanitized.len() > 1 && sanitized.ends_with(separator) {
+                sanitized.pop();
+            }
+        }
+        if let Some(min_len) = self.min_len
+            && let Some(filler) = filler
+        {
+            while sanitized.len() < min_len {
+                self.push_filler(&mut sanitized, filler);
+            }
+        }
+
+        if self.is_reserved(&sanitized)
+            && let Some(filler) = filler
+        {
+            if self
+                .max_len
+                .is_some_and(|max_len| sanitized.len() >= max_len)
+            {
+                sanitized.pop();
+            }
+            self.push_filler(&mut sanitized, filler);
+        }
+
+        sanitized
+    }
+
+    /// Returns [`sanitize`] applied to `value` if `is_valid_id` accepts it, or
+    /// else the fixed fallback value `sanitize("")`.
+    ///
+    /// This is used by the generated `from_lossy`, which passes the type's
+    /// full `is_valid_id`, predicate included.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `is_valid_id` rejects the fallback value.
+    ///
+    /// [`sanitize`]: IdRules::sanitize
+    #[doc(hidden)]
+    #[track_caller]
+    pub fn sanitize_valid(
+        &self,
+        value: &str,
+        type_name: &str,
+        is_valid_id: impl Fn(&str) -> bool,
+    ) -> String {
+        let sanitized = self.sanitize(value);
+        if is_valid_id(&sanitized) {
+            return sanitized;
+        }
+
+        let fallback = self.sanitize("");
+        assert!(
+            is_valid_id(&fallback),
+            "`{fallback}` is not a valid `{type_name}`, so `from_lossy` has no fallback value."
+        );
+        fallback
+    }
+
+    /// Returns an ID that losslessly encodes arbitrary text.
+    ///
+    /// Characters that are allowed by these rules are kept, and other
+    /// characters are escaped as `_`, the uppercase hexadecimal Unicode code
+    /// point, and `_`, e.g. `/` becomes `_2F_`. An `_` that would otherwise be
+    /// read as the start of an escape sequence is itself escaped as `_5F_`.
+    /// If the result would be a reserved word or begin with a reserved prefix,
+    /// the first character is escaped.
+    ///
+    /// Distinct values always have distinct encodings, and
+    /// [`IdRules::decode`] returns the original value. Valid IDs are returned
+    /// unchanged, unless they contain an `_` followed by uppercase hexadecimal
+    /// digits and another `_`.
+    ///
+    /// The result only satisfies these rules if they allow `_`, `0-9`, and
+    /// `A-F`, and it is within the length limits. This does not call the
+    /// predicate, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use id_newtype::IdRules;
+    ///
+    /// let rules = IdRules::new();
+    /// let encoded = rules.encode("src/main.rs");
+    ///
+    /// assert_eq!("src_2F_main_2E_rs", encoded);
+    /// assert_eq!(Ok(String::from("src/main.rs")), IdRules::decode(&encoded));
+    /// assert_eq!("web_server", rules.encode("web_server"));
+    /// ```
+    pub fn encode(&self, value: &str) -> String {
+        let encoded = self.encode_with(value, false);
+        if self.is_reserved(&encoded) || self.reserved_prefix(&encoded).is_some() {
+            self.encode_with(value, true)
+        } else {
+            encoded
+        }
+    }
+
+    /// Returns the original value of an ID produced by [`IdRules::encode`].
+    ///
+    /// Text that is not an escape sequence is returned unchanged.
+    pub fn decode(id: &str) -> Result<String, IdDecodeError> {
+        let mut decoded = String::with_capacity(id.len());
+        let mut offset = 0;
+        while let Some(c) = id[offset..].chars().next() {
+            let escape_len = (c == '_')
+                .then(|| Self::escape_len(&id.as_bytes()[offset..]))
+                .flatten();
+            match escape_len {
+                Some(escape_len) => {
+                    let escape = &id[offset..offset + escape_len];
+                    let c = u32::from_str_radix(&escape[1..escape_len - 1], 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .ok_or_else(|| IdDecodeError::new(escape.to_string(), offset))?;
+                    decoded.push(c);
+                    offset += escape_len;
+                }
+                None => {
+                    decoded.push(c);
+                    offset += c.len_utf8();
+                }
+            }
+        }
+
+        Ok(decoded)
+    }
+
+    /// Returns the encoding of `value`, escaping the first character if
+    /// `escape_first` is `true`.
+    fn encode_with(&self, value: &str, escape_first: bool) -> String {
+        // Encoded from the end, as whether an `_` is escaped depends on what follows
+        // it.
+        let mut encoded_rev = Vec::with_capacity(value.len());
+        for (offset, c) in value.char_indices().rev() {
+            let is_allowed = if offset == 0 {
+                !escape_first && self.first.contains(c)
+            } else {
+                self.rest.contains(c)
+            };
+            let is_literal = is_allowed
+                && (c != '_'
+                    || Self::escape_len(std::iter::once(&b'_').chain(encoded_rev.iter().rev()))
+                        .is_none());
+
+            if is_literal {
+                // Allowed characters are always ASCII.
+                encoded_rev.push(c as u8);
+            } else {
+                let escape = format!("_{:X}_", u32::from(c));
+                encoded_rev.extend(escape.bytes().rev());
+            }
+        }
+        encoded_rev.reverse();
+
+        String::from_utf8(encoded_rev).expect("Encoded IDs only contain ASCII characters.")
+    }
+
+    /// Returns the length of the escape sequence at the start of `bytes`, if
+    /// any.
+    ///
+    /// An escape sequence is an `_`, one or more uppercase hexadecimal digits,
+    /// and an `_`.
+    fn escape_len<'b>(bytes: impl IntoIterator<Item = &'b u8>) -> Option<usize> {
+        let mut bytes = bytes.into_iter();
+        if bytes.next() != Some(&b'_') {
+            return None;
+        }
+
+        let mut hex_len = 0;
+        for b in bytes {
+            match b {
+                b'0'..=b'9' | b'A'..=b'F' => hex_len += 1,
+                b'_' if hex_len > 0 => return Some(hex_len + 2),
+                _ => return None,
+            }
+        }
+        None
+    }
+
+    /// Appends `filler`, or another allowed character if `filler` would make
+    /// the value begin with a reserved prefix.
+    fn push_filler(&self, sanitized: &mut String, filler: char) {
+        let c = [filler]
+            .into_iter()
+            .chain(self.rest.chars())
+            .find(|c| {
+                !self.reserved_prefixes.iter().any(|reserved_prefix| {
+                    reserved_prefix.len() == sanitized.len() + 1
+                        && reserved_prefix.starts_with(sanitized.as_str())
+                        && reserved_prefix.ends_with(*c)
+                })
+            })
+            .unwrap_or(filler);
+        sanitized.push(c);
+    }
+
+    /// Returns the reason that the byte at `offset` breaks the case style's
+    /// rules, if any.
+    const fn style_violation(&self, bytes: &[u8], offset: usize) -> Option<InvalidReason> {
+        match self.style {
+            Some(style) => style.violation(bytes, offset),
+            None => None,
+        }
+    }
+
+    /// Returns the reserved prefix that the provided `&str` begins with, if
+    /// any.
+    const fn reserved_prefix(&self, proposed_id: &str) -> Option<&'static str> {
+        let mut i = 0;
+        while i < self.reserved_prefixes.len() {
+            if const_str::starts_with(proposed_id, self.reserved_prefixes[i]) {
+                return Some(self.reserved_prefixes[i]);
+            }
+            i += 1;
+        }
+        None
+    }
+
+    /// Returns whether the provided `&str` is a reserved word.
+    const fn is_reserved(&self, proposed_id: &str) -> bool {
+        let mut i = 0;
+        while i < self.reserved.len() {
+            if const_str::eq(self.reserved[i], proposed_id) {
+                return true;
+            }
+            i += 1;
+        }
+
+        let mut i = 0;
+        while i < self.reserved_sets.len() {
+            if self.reserved_sets[i].contains(proposed_id) {
+                return true;
+            }
+            i += 1;
+        }
+        false
+    }
+}
+
+impl Default for IdRules {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Describes the rules, e.g. "must begin with a letter or underscore, and
+/// contain only letters, numbers, or underscores".
+impl fmt::Display for IdRules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "must begin with {}", self.first.describe_one())?;
+        if self.rest.is_empty() {
+            write!(f, ", and contain no other characters")?;
+        } else {
+            write!(f, ", and contain only {}", self.rest.describe_any())?;
+        }
+        match (self.min_len, self.max_len) {
+            (Some(min_len), Some(max_len)) => {
+                write!(f, ", and be {min_len} to {max_len} characters long")?
+            }
+            (Some(min_len), None) => write!(f, ", and be at least {min_len} characters long")?,
+            (None, Some(max_len)) => write!(f, ", and be at most {max_len} characters long")?,
+            (None, None) => {}
+        }
+        if let Some(style) = self.style {
+            write!(f, ", and be `{style}`")?;
+            if style.has_underscore_rules() {
+                write!(f, " without repeated or trailing underscores")?;
+            }
+        }
+        if !self.reserved.is_empty() || !self.reserved_sets.is_empty() {
+            write!(f, ", and not be a reserved word")?;
+        }
+        match self.reserved_prefixes {
+            [] => {}
+            [reserved_prefix] => write!(f, ", and not begin with `{reserved_prefix}`")?,
+            [reserved_prefix_0, reserved_prefix_1] => write!(
+                f,
+                ", and not begin with `{reserved_prefix_0}` or `{reserved_prefix_1}`"
+            )?,
+            [reserved_prefixes @ .., reserved_prefix_last] => {
+                write!(f, ", and not begin with ")?;
+                reserved_prefixes
+                    .iter()
+                    .try_for_each(|reserved_prefix| write!(f, "`{reserved_prefix}`, "))?;
+                write!(f, "or `{reserved_prefix_last}`")?;
+            }
+        }
+        if let Some(predicate_name) = self.predicate_name {
+            write!(f, ", and satisfy `{predicate_name}`")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CaseStyle, IdViolation, InvalidReason, ReservedSet};
+
+    use super::IdRules;
+
+    #[test]
+    fn default_is_valid_id() {
+        let rules = IdRules::DEFAULT;
+
+        assert!(rules.is_valid_id("_abc_123"));
+        assert!(!rules.is_valid_id(""));
+        assert!(!rules.is_valid_id("1abc"));
+        assert!(!rules.is_valid_id("a-b"));
+    }
+
+    #[test]
+    fn custom_is_valid_id() {
+        let rules = IdRules::new().first("a-z").rest("a-z0-9-");
+
+        assert!(rules.is_valid_id("web-server-2"));
+        assert!(!rules.is_valid_id("_web"));
+        assert!(!rules.is_valid_id("Web"));
+        assert!(!rules.is_valid_id("web_server"));
+    }
+
+    #[test]
+    fn validate() {
+        let rules = IdRules::DEFAULT;
+
+        assert_eq!(Ok(()), rules.validate("abc"));
+        assert_eq!(
+            Err(IdViolation::new(InvalidReason::Empty, 0, None)),
+            rules.validate("")
+        );
+        assert_eq!(
+            Err(IdViolation::new(
+                InvalidReason::InvalidFirstChar,
+                0,
+                Some('1')
+            )),
+            rules.validate("1abc")
+        );
+        assert_eq!(
+            Err(IdViolation::new(InvalidReason::InvalidChar, 3, Some('é'))),
+            rules.validate("café")
+        );
+    }
+
+    #[test]
+    fn validate_length() {
+        let rules = IdRules::new().min_len(3).max_len(5);
+
+        assert_eq!(Ok(()), rules.validate("abc"));
+        assert_eq!(Ok(()), rules.validate("abcde"));
+        assert_eq!(
+            Err(IdViolation::new(InvalidReason::TooShort, 2, None)),
+            rules.validate("ab")
+        );
+        assert_eq!(
+            Err(IdViolation::new(InvalidReason::TooLong, 5, None)),
+            rules.validate("abcdef")
+        );
+        // length is checked before characters
+        assert_eq!(
+            Err(IdViolation::new(InvalidReason::TooLong, 4, None)),
+            rules.validate("abcdéf")
+        );
+    }
+
+    #[test]
+    fn validate_reserved() {
+        let rules = IdRules::new()
+            .reserved(&["internal"])
+            .reserved_prefixes(&["__", "tmp_"])
+            .reserved_sets(&[ReservedSet::Rust, ReservedSet::Sql]);
+
+        assert_eq!(Ok(()), rules.validate("web"));
+        assert_eq!(Ok(()), rules.validate("_internal"));
+        assert_eq!(
+            Err(IdViolation::new(InvalidReason::Reserved, 0, None)),
+            rules.validate("internal")
+        );
+        assert_eq!(
+            Err(IdViolation::new(InvalidReason::Reserved, 0, None)),
+            rules.validate("self")
+        );
+        assert_eq!(
+            Err(IdViolation::new(InvalidReason::Reserved, 0, None)),
+            rules.validate("Select")
+        );
+        assert_eq!(
+            Err(IdViolation::new(InvalidReason::ReservedPrefix, 2, None)),
+            rules.validate("__web")
+        );
+        assert_eq!(
+            Err(IdViolation::new(InvalidReason::ReservedPrefix, 4, None)),
+            rules.validate("tmp_web")
+        );
+    }
+
+    #[test]
+    fn display_reserved() {
+        let rules = IdRules::new()
+            .reserved_prefixes(&["__", "tmp_"])
+            .reserved_sets(&[ReservedSet::Rust]);
+
+        assert_eq!(
+            "must begin with a letter or underscore, and contain only letters, numbers, or underscores, \
+            and not be a reserved word, and not begin with `__` or `tmp_`",
+            rules.to_string()
+        );
+    }
+
+    #[test]
+    fn sanitize_default() {
+        let rules = IdRules::DEFAULT;
+
+        [
+            ("Web Server #2", "Web_Server_2"),
+            ("3rd-party API", "_3rd_party_API"),
+            ("Crème brûlée", "Creme_brulee"),
+            ("  padded  ", "padded"),
+            ("a - b", "a_b"),
+            ("already__valid_", "already__valid"),
+            ("日本", "_"),
+            ("", "_"),
+        ]
+        .into_iter()
+        .for_each(|(value, expected)| {
+            let sanitized = rules.sanitize(value);
+            assert_eq!(expected, sanitized);
+            assert!(rules.is_valid_id(&sanitized));
+        });
+    }
+
+    #[test]
+    fn sanitize_custom() {
+        let rules = IdRules::new().first("a-z").rest("a-z0-9-");
+
+        [
+            ("Web Server #2", "web-server-2"),
+            ("Straße_1", "strasse-1"),
+            ("3rd-party", "a3rd-party"),
+            ("web-", "web"),
+            ("Web Server -", "web-server"),
+            ("-", "a"),
+        ]
+        .into_iter()
+        .for_each(|(value, expected)| {
+            let sanitized = rules.sanitize(value);
+            assert_eq!(expected, sanitized);
+            assert!(rules.is_valid_id(&sanitized));
+        });
+    }
+
+    #[test]
+    fn sanitize_length_and_reserved() {
+        let rules = IdRules::new()
+            .min_len(2)
+            .max_len(8)
+            .reserved_prefixes(&["__"])
+            .reserved_sets(&[ReservedSet::Rust]);
+
+        [
+            ("a", "a_"),
+            ("abcdefghij", "abcdefgh"),
+            ("abcdefg hij", "abcdefg"),
+            ("__init__", "init"),
+            ("fn", "fn_"),
+            ("1", "_1"),
+            ("", "_0"),
+        ]
+        .into_iter()
+        .for_each(|(value, expected)| {
+            let sanitized = rules.sanitize(value);
+            assert_eq!(expected, sanitized);
+            assert!(rules.is_valid_id(&sanitized), "{sanitized}");
+        });
+    }
+
+    #[test]
+    fn sanitize_style() {
+        let rules = IdRules::new().style(CaseStyle::Snake).min_len(3);
+
+        [
+            ("Web Server #2", "web_server_2"),
+            ("__already__valid_", "already_valid"),
+            ("WebServer", "webserver"),
+            ("2", "a20"),
+            ("", "a00"),
+        ]
+        .into_iter()
+        .for_each(|(value, expected)| {
+            let sanitized = rules.sanitize(value);
+            assert_eq!(expected, sanitized);
+            assert!(rules.is_valid_id(&sanitized), "{sanitized}");
+        });
+
+        let rules = IdRules::new().style(CaseStyle::ScreamingSnake);
+        assert_eq!("WEB_SERVER", rules.sanitize("web-server-"));
+    }
+
+    #[test]
+    fn encode_round_trips() {
+        let rules = IdRules::DEFAULT;
+
+        [
+            ("web_server", "web_server"),
+            ("src/main.rs", "src_2F_main_2E_rs"),
+            ("user@example.com", "user_40_example_2E_com"),
+            ("3rd", "_33_rd"),
+            ("café", "caf_E9_"),
+            ("a_2F_b", "a_5F_2F_b"),
+            ("_", "_"),
+            ("__", "__"),
+        ]
+        .into_iter()
+        .for_each(|(value, expected)| {
+            let encoded = rules.encode(value);
+            assert_eq!(expected, encoded);
+            assert!(rules.is_valid_id(&encoded), "{encoded}");
+            assert_eq!(Ok(String::from(value)), IdRules::decode(&encoded));
+        });
+    }
+
+    #[test]
+    fn encode_is_injective() {
+        let rules = IdRules::DEFAULT;
+        let values = [
+            "a/b", "a_2F_b", "a_b", "a_5F_b", "a__b", "_2F_", "/", "A", "_41_",
+        ];
+
+        let encoded = values
+            .iter()
+            .map(|value| rules.encode(value))
+            .collect::<std::collections::HashSet<_>>();
+
+        assert_eq!(values.len(), encoded.len());
+    }
+
+    #[test]
+    fn encode_escapes_reserved() {
+        let rules = IdRules::new()
+            .reserved_prefixes(&["__"])
+            .reserved_sets(&[ReservedSet::Rust]);
+
+        assert_eq!("_66_n", rules.encode("fn"));
+        assert_eq!("_5F__x", rules.encode("__x"));
+        assert_eq!(Ok(String::from("fn")), IdRules::decode("_66_n"));
+        assert_eq!(Ok(String::from("__x")), IdRules::decode("_5F__x"));
+    }
+
+    #[test]
+    fn decode_invalid_escape() {
+        let error = IdRules::decode("a_D800_").unwrap_err();
+
+        assert_eq!("_D800_", error.escape());
+        assert_eq!(1, error.offset());
+        assert_eq!(
+            "`_D800_` at offset 1 is not a valid escaped character.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn display_default() {
+        assert_eq!(
+            "must begin with a letter or underscore, and contain only letters, numbers, or underscores",
+            IdRules::DEFAULT.to_string()
+        );
+    }
+
+    #[test]
+    fn display_length() {
+        assert_eq!(
+            "must begin with a letter or underscore, and contain only letters, numbers, or underscores, \
+            and be 3 to 5 characters long",
+            IdRules::new().min_len(3).max_len(5).to_string()
+        );
+        assert_eq!(
+            "must begin with a letter or underscore, and contain only letters, numbers, or underscores, \
+            and be at most 5 characters long",
+            IdRules::new().max_len(5).to_string()
+        );
+    }
+
+    #[test]
+    fn validate_style() {
+        let rules = IdRules::new().style(CaseStyle::Snake);
+
+        assert_eq!(Ok(()), rules.validate("web_server_2"));
+        assert_eq!(
+            Err(IdViolation::new(
+                InvalidReason::UppercaseLetter,
+                0,
+                Some('W')
+            )),
+            rules.validate("WeB__x_")
+        );
+        assert_eq!(
+            Err(IdViolation::new(
+                InvalidReason::RepeatedUnderscore,
+                4,
+                Some('_')
+            )),
+            rules.validate("web__x_")
+        );
+        assert_eq!(
+            Err(IdViolation::new(
+                InvalidReason::TrailingUnderscore,
+                5,
+                Some('_')
+            )),
+            rules.validate("web_x_")
+        );
+        assert_eq!(
+            Err(IdViolation::new(
+                InvalidReason::InvalidFirstChar,
+                0,
+                Some('_')
+            )),
+            rules.validate("_web")
+        );
+        assert_eq!(
+            Err(IdViolation::new(InvalidReason::InvalidChar, 3, Some('-'))),
+            rules.validate("web-x")
+        );
+    }
+
+    #[test]
+    fn display_style() {
+        assert_eq!(
+            "must begin with a lowercase letter, and contain only lowercase letters, numbers, or underscores, \
+            and be `snake_case` without repeated or trailing underscores",
+            IdRules::new().style(CaseStyle::Snake).to_string()
+        );
+        assert_eq!(
+            "must begin with an uppercase letter, and contain only letters or numbers, and be `PascalCase`",
+            IdRules::new().style(CaseStyle::Pascal).to_string()
+        );
+    }
+
+    #[test]
+    fn display_custom() {
+        let rules = IdRules::new()
+            .first("a-z")
+            .rest("a-z0-9-")
+            .with_predicate_name("no_double_hyphen");
+
+        assert_eq!(
+            "must begin with a lowercase letter, and contain only lowercase letters, numbers, or hyphens, \
+            and satisfy `no_double_hyphen`",
+            rules.to_string()
+        );
+    }
+
+    #[test]
+    fn pattern_lengths() {
+        let pattern = |rules: IdRules| rules.first("a-z").rest("a-z0-9").pattern();
+
+        assert_eq!("^[a-z][a-z0-9]*$", pattern(IdRules::new()));
+        assert_eq!("^[a-z][a-z0-9]+$", pattern(IdRules::new().min_len(2)));
+        assert_eq!("^[a-z][a-z0-9]{2,}$", pattern(IdRules::new().min_len(3)));
+        assert_eq!("^[a-z][a-z0-9]{0,4}$", pattern(IdRules::new().max_len(5)));
+        assert_eq!(
+            "^[a-z][a-z0-9]{2,4}$",
+            pattern(IdRules::new().min_len(3).max_len(5))
+        );
+        assert_eq!(
+            "^[a-z][a-z0-9]{4}$",
+            pattern(IdRules::new().min_len(5).max_len(5))
+        );
+        assert_eq!("^[a-z]$", pattern(IdRules::new().max_len(1)));
+        assert_eq!("^[a-z]$", IdRules::new().first("a-z").rest("").pattern());
+    }
+
+    #[test]
+    fn pattern_const() {
+        const RULES: IdRules = IdRules::new().first("a-z").rest("a-z0-9-");
+        const PATTERN: [u8; RULES.pattern_len()] = RULES.pattern_bytes();
+
+        assert_eq!(b"^[a-z][a-z0-9\\-]*$", &PATTERN);
+    }
+}
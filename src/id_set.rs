@@ -0,0 +1,73 @@
+use std::{
+    borrow::Borrow,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    hash::{BuildHasher, Hash},
+};
+
+/// Collection of IDs that may be queried by `&str`, used by
+/// [`IdNewtype::uniquify_in`].
+///
+/// This is implemented for sets of IDs, maps keyed by IDs, and slices of
+/// anything that may be read as a `&str`.
+///
+/// [`IdNewtype::uniquify_in`]: crate::IdNewtype::uniquify_in
+pub trait IdSet {
+    /// Returns whether the collection contains the given ID.
+    fn contains_id(&self, id: &str) -> bool;
+}
+
+impl<T, S> IdSet for HashSet<T, S>
+where
+    T: Borrow<str> + Hash + Eq,
+    S: BuildHasher,
+{
+    fn contains_id(&self, id: &str) -> bool {
+        self.contains(id)
+    }
+}
+
+impl<T> IdSet for BTreeSet<T>
+where
+    T: Borrow<str> + Ord,
+{
+    fn contains_id(&self, id: &str) -> bool {
+        self.contains(id)
+    }
+}
+
+impl<K, V, S> IdSet for HashMap<K, V, S>
+where
+    K: Borrow<str> + Hash + Eq,
+    S: BuildHasher,
+{
+    fn contains_id(&self, id: &str) -> bool {
+        self.contains_key(id)
+    }
+}
+
+impl<K, V> IdSet for BTreeMap<K, V>
+where
+    K: Borrow<str> + Ord,
+{
+    fn contains_id(&self, id: &str) -> bool {
+        self.contains_key(id)
+    }
+}
+
+impl<T> IdSet for [T]
+where
+    T: AsRef<str>,
+{
+    fn contains_id(&self, id: &str) -> bool {
+        self.iter().any(|existing| existing.as_ref() == id)
+    }
+}
+
+impl<T> IdSet for Vec<T>
+where
+    T: AsRef<str>,
+{
+    fn contains_id(&self, id: &str) -> bool {
+        self.as_slice().contains_id(id)
+    }
+}
@@ -0,0 +1,159 @@
+use std::{borrow::Cow, ops::Deref, rc::Rc, sync::Arc};
+
+use crate::Interner;
+
+/// Storage for the string of an ID type, which is the type of its field.
+///
+/// ID types declared as `id_newtype!(MyId(Arc<str>), MyIdInvalidFmt)` store
+/// their value in the given type:
+///
+/// ```rust
+/// use std::sync::Arc;
+///
+/// #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// pub struct MyId(Arc<str>);
+///
+/// id_newtype::id_newtype!(MyId(Arc<str>), MyIdInvalidFmt);
+///
+/// let my_id = MyId::new("web").unwrap();
+/// let my_id_clone = my_id.clone(); // Does not allocate.
+///
+/// assert_eq!("web", my_id_clone.as_str());
+/// assert!(Arc::ptr_eq(&my_id.into_inner(), &my_id_clone.into_inner()));
+/// ```
+pub trait IdStorage: Deref<Target = str> {
+    /// Returns the storage for a `&'static str`.
+    fn from_static(s: &'static str) -> Self;
+
+    /// Returns the storage for an owned `String`.
+    fn from_string(s: String) -> Self;
+
+    /// Returns the storage for a copy of a borrowed `&str`.
+    fn from_borrowed(s: &str) -> Self
+    where
+        Self: Sized,
+    {
+        Self::from_string(String::from(s))
+    }
+}
+
+impl IdStorage for Cow<'static, str> {
+    fn from_static(s: &'static str) -> Self {
+        Cow::Borrowed(s)
+    }
+
+    fn from_string(s: String) -> Self {
+        Cow::Owned(s)
+    }
+}
+
+impl IdStorage for String {
+    fn from_static(s: &'static str) -> Self {
+        String::from(s)
+    }
+
+    fn from_string(s: String) -> Self {
+        s
+    }
+}
+
+impl IdStorage for Box<str> {
+    fn from_static(s: &'static str) -> Self {
+        Box::from(s)
+    }
+
+    fn from_string(s: String) -> Self {
+        s.into_boxed_str()
+    }
+
+    fn from_borrowed(s: &str) -> Self {
+        Box::from(s)
+    }
+}
+
+impl IdStorage for Arc<str> {
+    fn from_static(s: &'static str) -> Self {
+        Arc::from(s)
+    }
+
+    fn from_string(s: String) -> Self {
+        Arc::from(s)
+    }
+
+    fn from_borrowed(s: &str) -> Self {
+        Arc::from(s)
+    }
+}
+
+impl IdStorage for Rc<str> {
+    fn from_static(s: &'static str) -> Self {
+        Rc::from(s)
+    }
+
+    fn from_string(s: String) -> Self {
+        Rc::from(s)
+    }
+
+    fn from_borrowed(s: &str) -> Self {
+        Rc::from(s)
+    }
+}
+
+/// Owned and borrowed strings are interned, as they must live for the rest of
+/// the program, so each distinct string is leaked once. This is intended for
+/// IDs that are mostly compile time constants.
+impl IdStorage for &'static str {
+    fn from_static(s: &'static str) -> Self {
+        s
+    }
+
+    fn from_string(s: String) -> Self {
+        Self::from_borrowed(&s)
+    }
+
+    fn from_borrowed(s: &str) -> Self {
+        let interner = Interner::for_type::<&'static str>();
+        interner.resolve(interner.intern(s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{borrow::Cow, sync::Arc};
+
+    use super::IdStorage;
+
+    #[test]
+    fn cow_borrows_static_str() {
+        assert!(matches!(
+            <Cow<'static, str>>::from_static("web"),
+            Cow::Borrowed("web")
+        ));
+        assert!(matches!(
+            <Cow<'static, str>>::from_borrowed("web"),
+            Cow::Owned(_)
+        ));
+    }
+
+    #[test]
+    fn storages_hold_the_string() {
+        assert_eq!("web", &*<Arc<str>>::from_borrowed("web"));
+        assert_eq!("web", &*<Box<str>>::from_string(String::from("web")));
+        assert_eq!("web", &*String::from_static("web"));
+        assert_eq!("web", <&'static str>::from_string(String::from("web")));
+    }
+
+    #[test]
+    fn static_str_leaks_each_string_once() {
+        let web = <&'static str>::from_string(String::from("storage_web"));
+
+        assert!(std::ptr::eq(
+            web,
+            <&'static str>::from_borrowed("storage_web")
+        ));
+        assert!(std::ptr::eq(
+            web,
+            <&'static str>::from_string(String::from("storage_web"))
+        ));
+    }
+}
@@ -0,0 +1,191 @@
+use std::fmt;
+
+use crate::{const_str, IdRules, InvalidReason};
+
+/// Describes why and where a value is not a valid ID.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct IdViolation {
+    /// Reason that the value is not valid.
+    reason: InvalidReason,
+    /// Byte offset of the offending part of the value.
+    offset: usize,
+    /// The offending character, if the violation is for a single character.
+    invalid_char: Option<char>,
+}
+
+impl IdViolation {
+    /// Returns a new `IdViolation`.
+    pub const fn new(reason: InvalidReason, offset: usize, invalid_char: Option<char>) -> Self {
+        Self {
+            reason,
+            offset,
+            invalid_char,
+        }
+    }
+
+    /// Returns the reason that the value is not valid.
+    pub const fn reason(&self) -> InvalidReason {
+        self.reason
+    }
+
+    /// Returns the byte offset of the offending part of the value.
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the offending character, if the violation is for a single
+    /// character.
+    pub const fn invalid_char(&self) -> Option<char> {
+        self.invalid_char
+    }
+
+    /// Returns the 1-based column of the offending part of the value.
+    pub fn column(&self, value: &str) -> usize {
+        value
+            .get(..self.offset)
+            .map(|prefix| prefix.chars().count())
+            .unwrap_or(self.offset)
+            + 1
+    }
+
+    /// Returns the part of the value to keep in an error.
+    ///
+    /// For [`InvalidReason::TooLong`], this is truncated to the maximum length,
+    /// so that large inputs are not copied into the error.
+    #[doc(hidden)]
+    pub fn error_value<'s>(&self, value: &'s str) -> &'s str {
+        match self.reason {
+            InvalidReason::TooLong => value.get(..self.offset).unwrap_or(value),
+            _ => value,
+        }
+    }
+
+    /// Panics with the first line of the error message for an invalid ID,
+    /// used by the `new_const` constructors generated by `id_newtype!`.
+    ///
+    /// When used in a `const`, this is a compile error.
+    #[doc(hidden)]
+    #[track_caller]
+    pub const fn panic(&self, ty_name: &str, value: &str) -> ! {
+        let mut message = ConstMessage::new();
+        message.push_str("`");
+        if let InvalidReason::TooLong = self.reason {
+            message.push_str(value.split_at(self.offset).0);
+            message.push_str("...");
+        } else {
+            message.push_str(value);
+        }
+        message.push_str("` is not a valid `");
+        message.push_str(ty_name);
+        message.push_str("`: ");
+        message.push_str(self.reason.as_str());
+        if let Some(invalid_char) = self.invalid_char {
+            message.push_str(" `");
+            message.push_str(invalid_char.encode_utf8(&mut [0; 4]));
+            message.push_str("` at column ");
+            message.push_usize(const_str::char_count(value, self.offset) + 1);
+        }
+        message.push_str(".");
+
+        panic!("{}", message.as_str())
+    }
+
+    /// Writes the error message for an invalid ID, used by the error types
+    /// generated by `id_newtype!`.
+    ///
+    /// ```text
+    /// `a b` is not a valid `MyId`: invalid character ` ` at column 2.
+    ///     a b
+    ///      ^
+    /// `MyId`s must begin with a letter or underscore, and contain only letters, numbers, or underscores.
+    /// ```
+    #[doc(hidden)]
+    pub fn fmt_error(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        ty_name: &str,
+        value: &str,
+        rules: &IdRules,
+    ) -> fmt::Result {
+        if self.reason == InvalidReason::TooLong {
+            let value = value.get(..self.offset).unwrap_or(value);
+            write!(
+                f,
+                "`{value}...` is not a valid `{ty_name}`: {}",
+                self.reason
+            )?;
+        } else {
+            write!(f, "`{value}` is not a valid `{ty_name}`: {}", self.reason)?;
+        }
+        if let Some(invalid_char) = self.invalid_char {
+            let column = self.column(value);
+            let indent = column - 1;
+            write!(
+                f,
+                " `{invalid_char}` at column {column}.\n    {value}\n    {:indent$}^",
+                ""
+            )?;
+        } else {
+            write!(f, ".")?;
+        }
+
+        write!(f, "\n`{ty_name}`s {rules}.")
+    }
+}
+
+/// Fixed capacity message buffer, as `String` cannot be used in `const fn`s.
+struct ConstMessage {
+    bytes: [u8; Self::CAPACITY],
+    len: usize,
+}
+
+impl ConstMessage {
+    /// Maximum length of the message in bytes; longer messages are truncated.
+    const CAPACITY: usize = 512;
+
+    const fn new() -> Self {
+        Self {
+            bytes: [0; Self::CAPACITY],
+            len: 0,
+        }
+    }
+
+    const fn push_str(&mut self, s: &str) {
+        let bytes = s.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() && self.len < Self::CAPACITY {
+            self.bytes[self.len] = bytes[i];
+            self.len += 1;
+            i += 1;
+        }
+    }
+
+    const fn push_usize(&mut self, mut n: usize) {
+        let mut digits = [0u8; 20];
+        let mut start = digits.len();
+        loop {
+            start -= 1;
+            digits[start] = b'0' + (n % 10) as u8;
+            n /= 10;
+            if n == 0 {
+                break;
+            }
+        }
+        match std::str::from_utf8(digits.split_at(start).1) {
+            Ok(digits) => self.push_str(digits),
+            Err(_) => unreachable!(),
+        }
+    }
+
+    const fn as_str(&self) -> &str {
+        let bytes = self.bytes.split_at(self.len).0;
+        match std::str::from_utf8(bytes) {
+            Ok(message) => message,
+            // The message was truncated in the middle of a character.
+            Err(error) => match std::str::from_utf8(bytes.split_at(error.valid_up_to()).0) {
+                Ok(message) => message,
+                Err(_) => "",
+            },
+        }
+    }
+}
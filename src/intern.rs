@@ -0,0 +1,25 @@
+use crate::{Interned, Interner};
+
+/// ID types that can be interned as an [`Interned`] handle.
+///
+/// This is implemented by `id_newtype!` for each ID type, with an
+/// [`Interner`] per type.
+pub trait Intern: AsRef<str> + Sized {
+    /// This ID type with its lifetime, if any, as `'static`.
+    ///
+    /// Handles are for this type, so that borrowed IDs may be interned
+    /// without tying the handle to the borrow.
+    type Static: Intern<Static = Self::Static>;
+
+    /// Returns the interner for this ID type.
+    fn interner() -> &'static Interner;
+
+    /// Returns the ID for a string that was interned from a valid ID.
+    #[doc(hidden)]
+    fn from_interned(s: &'static str) -> Self;
+
+    /// Returns the interned handle for this ID.
+    fn intern(&self) -> Interned<Self::Static> {
+        Interned::new(self)
+    }
+}
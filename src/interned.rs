@@ -0,0 +1,146 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+};
+
+use crate::Intern;
+
+/// `Copy` handle to an interned ID, which compares and hashes as an integer.
+///
+/// Handles are ordered by when their ID was first interned, not by the ID's
+/// text. For ID types with a lifetime, handles are for the `'static` type, e.g.
+/// `Interned<MyId<'static>>`.
+///
+/// ```rust
+/// use std::borrow::Cow;
+///
+/// use id_newtype::{Intern, Interned};
+///
+/// #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// pub struct MyId(Cow<'static, str>);
+///
+/// id_newtype::id_newtype!(MyId, MyIdInvalidFmt);
+///
+/// let web: Interned<MyId> = MyId::new("web").unwrap().intern();
+///
+/// assert_eq!(web, Interned::new(&MyId::new("web").unwrap()));
+/// assert_eq!("web", web.resolve());
+/// assert_eq!(MyId::new("web").unwrap(), web.to_id());
+/// ```
+pub struct Interned<T> {
+    /// Index of the ID in `T`'s interner.
+    index: u32,
+    /// Marker for the ID type.
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Interned<T>
+where
+    T: Intern,
+{
+    /// Returns the handle for the given ID, interning it if it is not already
+    /// interned.
+    pub fn new<I>(id: &I) -> Self
+    where
+        I: Intern<Static = T>,
+    {
+        Self::from_index(T::interner().intern(id.as_ref()))
+    }
+
+    /// Returns the handle for the given ID, if it is interned.
+    pub fn get<I>(id: &I) -> Option<Self>
+    where
+        I: Intern<Static = T>,
+    {
+        T::interner().get(id.as_ref()).map(Self::from_index)
+    }
+
+    /// Returns the interned string.
+    pub fn resolve(self) -> &'static str {
+        T::interner().resolve(self.index)
+    }
+
+    /// Returns the ID for this handle.
+    pub fn to_id(self) -> T {
+        T::from_interned(self.resolve())
+    }
+}
+
+impl<T> Interned<T> {
+    fn from_index(index: u32) -> Self {
+        Self {
+            index,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the index of the ID in its interner.
+    pub const fn index(self) -> u32 {
+        self.index
+    }
+}
+
+// These are implemented manually so that `T` does not need to implement them.
+
+impl<T> Clone for Interned<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Interned<T> {}
+
+impl<T> PartialEq for Interned<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl<T> Eq for Interned<T> {}
+
+impl<T> PartialOrd for Interned<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Interned<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.index.cmp(&other.index)
+    }
+}
+
+impl<T> Hash for Interned<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for Interned<T>
+where
+    T: Intern,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Interned").field(&self.resolve()).finish()
+    }
+}
+
+impl<T> fmt::Display for Interned<T>
+where
+    T: Intern,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.resolve())
+    }
+}
+
+impl<T> From<&T> for Interned<T::Static>
+where
+    T: Intern,
+{
+    fn from(id: &T) -> Self {
+        Self::new(id)
+    }
+}
@@ -0,0 +1,178 @@
+use std::{
+    any::TypeId,
+    collections::HashMap,
+    sync::{LazyLock, PoisonError, RwLock},
+};
+
+/// Thread safe map from strings to small integer handles.
+///
+/// Each distinct string is stored once for the rest of the program, and is
+/// given the next index. Strings are never removed, so this is intended for
+/// IDs that are reused, not for unbounded input.
+///
+/// Each ID type has its own `Interner`, which is used through [`Interned`].
+///
+/// [`Interned`]: crate::Interned
+#[derive(Debug, Default)]
+pub struct Interner {
+    state: RwLock<InternerState>,
+}
+
+#[derive(Debug, Default)]
+struct InternerState {
+    /// Index of each interned string.
+    indices: HashMap<&'static str, u32>,
+    /// Interned strings, by index.
+    strs: Vec<&'static str>,
+}
+
+impl Interner {
+    /// Returns a new empty `Interner`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the index of the given string, interning it if it is not
+    /// already interned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than `u32::MAX` strings are interned.
+    pub fn intern(&self, s: &str) -> u32 {
+        if let Some(index) = self.get(s) {
+            return index;
+        }
+
+        let mut state = self.state.write().unwrap_or_else(PoisonError::into_inner);
+        // Another thread may have interned the string since it was looked up.
+        if let Some(index) = state.indices.get(s) {
+            return *index;
+        }
+
+        let index = u32::try_from(state.strs.len())
+            .expect("Expected fewer than `u32::MAX` interned strings.");
+        let s: &'static str = Box::leak(Box::from(s));
+        state.strs.push(s);
+        state.indices.insert(s, index);
+        index
+    }
+
+    /// Returns the index of the given string, if it is interned.
+    pub fn get(&self, s: &str) -> Option<u32> {
+        let state = self.state.read().unwrap_or_else(PoisonError::into_inner);
+        state.indices.get(s).copied()
+    }
+
+    /// Returns the string at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index was not returned by this `Interner`.
+    pub fn resolve(&self, index: u32) -> &'static str {
+        let state = self.state.read().unwrap_or_else(PoisonError::into_inner);
+        state.strs[index as usize]
+    }
+
+    /// Returns the number of interned strings.
+    pub fn len(&self) -> usize {
+        let state = self.state.read().unwrap_or_else(PoisonError::into_inner);
+        state.strs.len()
+    }
+
+    /// Returns whether no strings are interned.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the interner for the type `T`, creating it the first time it is
+    /// requested.
+    ///
+    /// This is used for generic ID types, which cannot declare a `static`
+    /// interner for each type parameter.
+    pub(crate) fn for_type<T>() -> &'static Interner
+    where
+        T: ?Sized + 'static,
+    {
+        static INTERNERS: LazyLock<RwLock<HashMap<TypeId, &'static Interner>>> =
+            LazyLock::new(RwLock::default);
+
+        let type_id = TypeId::of::<T>();
+        let interners = INTERNERS.read().unwrap_or_else(PoisonError::into_inner);
+        if let Some(interner) = interners.get(&type_id) {
+            return interner;
+        }
+        drop(interners);
+
+        let mut interners = INTERNERS.write().unwrap_or_else(PoisonError::into_inner);
+        interners
+            .entry(type_id)
+            .or_insert_with(|| Box::leak(Box::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::Interner;
+
+    #[test]
+    fn intern_returns_same_index_for_same_string() {
+        let interner = Interner::new();
+
+        let web = interner.intern("web");
+        let db = interner.intern("db");
+
+        assert_eq!(web, interner.intern("web"));
+        assert_ne!(web, db);
+        assert_eq!("web", interner.resolve(web));
+        assert_eq!("db", interner.resolve(db));
+        assert_eq!(2, interner.len());
+    }
+
+    #[test]
+    fn get_does_not_intern() {
+        let interner = Interner::new();
+
+        assert_eq!(None, interner.get("web"));
+        assert!(interner.is_empty());
+
+        let web = interner.intern("web");
+        assert_eq!(Some(web), interner.get("web"));
+    }
+
+    #[test]
+    fn for_type_returns_one_interner_per_type() {
+        struct A;
+        struct B;
+
+        assert!(std::ptr::eq(
+            Interner::for_type::<A>(),
+            Interner::for_type::<A>()
+        ));
+        assert!(!std::ptr::eq(
+            Interner::for_type::<A>(),
+            Interner::for_type::<B>()
+        ));
+    }
+
+    #[test]
+    fn intern_is_consistent_across_threads() {
+        let interner = Interner::new();
+
+        let indices = thread::scope(|scope| {
+            let handles = (0..8)
+                .map(|_| scope.spawn(|| ["a", "b", "c"].map(|s| interner.intern(s))))
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+
+        assert_eq!(3, interner.len());
+        assert!(indices
+            .iter()
+            .all(|thread_indices| *thread_indices == indices[0]));
+    }
+}
@@ -0,0 +1,99 @@
+use std::{borrow::Cow, fmt, marker::PhantomData};
+
+use crate::{IdKind, IdViolation, InvalidReason};
+
+/// Error indicating a value is not a valid [`Id`] of kind `K`.
+///
+/// This is the generic equivalent of the error types generated by
+/// `id_newtype!`.
+///
+/// [`Id`]: crate::Id
+pub struct InvalidId<'s, K> {
+    /// String that was provided for the `Id`.
+    value: Cow<'s, str>,
+    /// Why and where the value is invalid.
+    violation: IdViolation,
+    /// Marker for the ID kind.
+    marker: PhantomData<fn() -> K>,
+}
+
+impl<'s, K> InvalidId<'s, K> {
+    /// Returns a new `InvalidId` error.
+    pub fn new(value: Cow<'s, str>, violation: IdViolation) -> Self {
+        Self {
+            value,
+            violation,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the value that failed to be parsed as an `Id`.
+    ///
+    /// For `TooLong` errors from `FromStr`, this is truncated to the maximum
+    /// length.
+    pub fn value(&self) -> &Cow<'s, str> {
+        &self.value
+    }
+
+    /// Returns why and where the value is invalid.
+    pub fn violation(&self) -> &IdViolation {
+        &self.violation
+    }
+
+    /// Returns the reason that the value is invalid.
+    pub fn reason(&self) -> InvalidReason {
+        self.violation.reason()
+    }
+
+    /// Returns the byte offset of the offending part of the value.
+    pub fn offset(&self) -> usize {
+        self.violation.offset()
+    }
+
+    /// Returns the offending character, if the error is for a single
+    /// character.
+    pub fn invalid_char(&self) -> Option<char> {
+        self.violation.invalid_char()
+    }
+}
+
+// These are implemented manually so that `K` does not need to implement them.
+
+impl<K> Clone for InvalidId<'_, K> {
+    fn clone(&self) -> Self {
+        Self::new(self.value.clone(), self.violation)
+    }
+}
+
+impl<K> PartialEq for InvalidId<'_, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value && self.violation == other.violation
+    }
+}
+
+impl<K> Eq for InvalidId<'_, K> {}
+
+impl<K> fmt::Debug for InvalidId<'_, K>
+where
+    K: IdKind,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InvalidId")
+            .field("kind", &K::TYPE_NAME)
+            .field("value", &self.value)
+            .field("violation", &self.violation)
+            .finish()
+    }
+}
+
+impl<K> fmt::Display for InvalidId<'_, K>
+where
+    K: IdKind,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.violation
+            .fmt_error(f, K::TYPE_NAME, &self.value, &K::RULES)
+    }
+}
+
+impl<K> std::error::Error for InvalidId<'_, K> where K: IdKind {}
@@ -0,0 +1,60 @@
+use std::fmt;
+
+/// Reason that a value is not a valid ID.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum InvalidReason {
+    /// The value is empty.
+    Empty,
+    /// The first character is not allowed at the start of an ID.
+    InvalidFirstChar,
+    /// A character after the first is not allowed in an ID.
+    InvalidChar,
+    /// The value is shorter than the minimum length.
+    TooShort,
+    /// The value is longer than the maximum length.
+    TooLong,
+    /// The value is a reserved word.
+    Reserved,
+    /// The value begins with a reserved prefix.
+    ReservedPrefix,
+    /// The value does not satisfy the ID type's predicate.
+    Predicate,
+    /// An uppercase letter is not allowed by the case style, e.g. in
+    /// `snake_case`.
+    UppercaseLetter,
+    /// A lowercase letter is not allowed by the case style, e.g. in
+    /// `SCREAMING_SNAKE_CASE`.
+    LowercaseLetter,
+    /// An underscore follows another underscore, which the case style does
+    /// not allow.
+    RepeatedUnderscore,
+    /// The value ends with an underscore, which the case style does not allow.
+    TrailingUnderscore,
+}
+
+impl InvalidReason {
+    /// Returns a short description of the reason, e.g. "invalid character".
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Empty => "the value is empty",
+            Self::InvalidFirstChar => "invalid first character",
+            Self::InvalidChar => "invalid character",
+            Self::TooShort => "the value is too short",
+            Self::TooLong => "the value is too long",
+            Self::Reserved => "the value is a reserved word",
+            Self::ReservedPrefix => "the value begins with a reserved prefix",
+            Self::Predicate => "the value does not satisfy the predicate",
+            Self::UppercaseLetter => "uppercase letter",
+            Self::LowercaseLetter => "lowercase letter",
+            Self::RepeatedUnderscore => "repeated underscore",
+            Self::TrailingUnderscore => "trailing underscore",
+        }
+    }
+}
+
+impl fmt::Display for InvalidReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
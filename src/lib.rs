@@ -1,11 +1,15 @@
 //! Implements logic for a `Cow<'static, str>` newtype where only `[A-Za-z0-9_]`
-//! are valid characters.
+//! are valid characters by default.
 //!
 //! Implementations are provided for:
 //!
 //! * `IdType::new`
 //! * `IdType::new_unchecked` (with `#[doc(hidden)]`)
 //! * `IdType::is_valid_id`
+//! * `IdType::PATTERN`
+//! * `IdType::from_lossy`
+//! * `IdType::encode`
+//! * `IdType::decode`
 //! * `IdType::into_inner`
 //! * `IdType::into_static`
 //! * `std::borrow::Borrow<str>`
@@ -14,12 +18,58 @@
 //! * `std::convert::TryFrom<&'static str>`
 //! * `std::fmt::Display`
 //! * `std::ops::Deref`
-//! * `std::ops::DerefMut`
 //! * `std::str::FromStr`
+//! * `id_newtype::IdNewtype`
+//! * `id_newtype::Intern`
 //!
 //! A separate error type is also generated, which indicates an invalid value
 //! when the ID type is instantiated with `new`.
 //!
+//! The error's `reason()`, `offset()`, and `invalid_char()` describe why and
+//! where the value is invalid, and its `Display` points at the offending
+//! column.
+//!
+//! [`IdNewtype`] ties these together, with the error type as
+//! `IdNewtype::Error`, so generic code such as
+//! `fn load<T: IdNewtype>(s: &str) -> Result<T, T::Error>` works with any ID
+//! type.
+//!
+//! [`IdNewtype::uniquify`] and [`IdNewtype::uniquify_in`] turn an ID that is
+//! taken into the first free variant with a numeric index, such as `web_2`,
+//! or return a [`UniquifyError`] if none is free, and
+//! [`IdNewtype::split_index`] splits `web_2` into `web` and `2`.
+//!
+//! [`IdNewtype::words`] iterates over the [`Words`] of an ID, and
+//! [`IdNewtype::display_case`] displays it in a [`CaseStyle`]. ID types that
+//! accept every case style may also convert between them with
+//! `to_snake_case`, `to_camel_case`, `to_pascal_case`, and
+//! `to_screaming_snake_case`, e.g. `web_server` becomes `WebServer`, and be
+//! constructed with `from_camel_case("webServer")` and friends.
+//!
+//! ID types are not `DerefMut`, as changing the value directly could make
+//! the ID invalid. Instead, [`IdNewtype::push_segment`],
+//! [`IdNewtype::replace_range`], and [`IdNewtype::edit`] change an ID in
+//! place, and [`IdNewtype::with_prefix`] and [`IdNewtype::with_suffix`] return
+//! a changed copy. The new value is validated, and on error, the ID is left
+//! unchanged.
+//!
+//! `from_lossy` turns arbitrary text such as `"Web Server #2"` into a valid ID
+//! such as `Web_Server_2`, as described by [`IdRules::sanitize`]. The result
+//! is checked with the type's predicate, and if it is rejected, the fixed
+//! fallback value `RULES.sanitize("")` is used instead.
+//!
+//! `encode` and `decode` losslessly map arbitrary text to and from an ID, by
+//! escaping other characters as `_`, the hexadecimal code point, and `_`, e.g.
+//! `"src/main.rs"` becomes `src_2F_main_2E_rs`. See [`IdRules::encode`] for
+//! details.
+//!
+//! `Intern` maps each ID to a `Copy` [`Interned`] handle, which compares and
+//! hashes as an integer, for IDs used as keys in large maps. `resolve` and
+//! `to_id` return the ID's text and the ID.
+//!
+//! IDs of up to 21 characters in `[A-Za-z0-9_]` can also be packed into a
+//! `u128` as a `Copy` [`PackedId`], without an interner.
+//!
 //!
 //! # Usage
 //!
@@ -50,8 +100,9 @@
 //! );
 //! ```
 //!
-//! If you have a procedural macro that checks for ID validity<sup>1</sup> at
-//! compile time, you may pass in its name as follows:
+//! If you pass in a macro name as the third argument, a `macro_rules!` macro
+//! with that name is generated, which checks ID validity<sup>1</sup> at
+//! compile time:
 //!
 //! ```rust
 //! #[macro_use]
@@ -59,10 +110,6 @@
 //!
 //! use std::borrow::Cow;
 //!
-//! // Either use `id_newtype::id`, or replace this with your own proc macro.
-//! use id_newtype::id;
-//! // use my_crate_static_check_macros::my_id;
-//!
 //! // Rename your ID type
 //! #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 //! pub struct MyId(Cow<'static, str>);
@@ -70,15 +117,60 @@
 //! id_newtype::id_newtype!(
 //!     MyId,           // Name of the ID type
 //!     MyIdInvalidFmt, // Name of the invalid value error
-//!     my_id           // Name of the proc macro
+//!     my_id           // Name of the compile time checked macro
 //! );
+//!
+//! # fn main() {
+//! let web_server = my_id!("web_server");
+//! # assert_eq!("web_server", web_server.as_str());
+//! # }
 //! ```
 //!
-//! <sup>1</sup> You can either enable the `"macros"` feature and have access to
-//! the `id!` macro, or implement your own proc macro. See
-//! [`id_newtype_macros`][macros_crate] for an example.
+//! The generated macro is usable after the `id_newtype!` invocation in the
+//! same module, and through its path (e.g. `crate::ids::my_id!`) elsewhere in
+//! the crate. The macro refers to the ID type by its name, so the type must be
+//! in scope where the macro is used, e.g. with `use crate::ids::MyId;`.
+//!
+//! The generated macro is crate-local: it is declared with `pub(crate) use`,
+//! so it cannot be used by other crates, even if the ID type is public. Other
+//! crates can use `checked_id!(my_crate::MyId, "web_server")` with the
+//! `"macros"` feature, or `MyId::new_const("web_server")` in a `const`.
+//!
+//! <sup>1</sup> The generated macro checks the ID by `const` evaluation of the
+//! type's `validate`, with or without the `"macros"` feature, so the check
+//! always uses the type's own rules.
+//!
+//! ## Const Construction
+//!
+//! `is_valid_id` and `validate` are `const fn`s, and `new_const` panics when
+//! the value is invalid, so IDs in `const` items are checked by the compiler
+//! even without the `"macros"` feature:
+//!
+//! ```rust
+//! use std::borrow::Cow;
+//!
+//! #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+//! pub struct MyId(Cow<'static, str>);
+//!
+//! id_newtype::id_newtype!(MyId, MyIdInvalidFmt);
 //!
-//! [macros_crate]: https://github.com/azriel91/id_newtype/id_newtype_macros
+//! const WEB: MyId = MyId::new_const("web");
+//! const IS_VALID: bool = MyId::is_valid_id("web_server");
+//! # assert_eq!("web", WEB.as_str());
+//! # assert!(IS_VALID);
+//! ```
+//!
+//! ```rust,compile_fail,E0080
+//! # use std::borrow::Cow;
+//! #
+//! # #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+//! # pub struct MyId(Cow<'static, str>);
+//! #
+//! # id_newtype::id_newtype!(MyId, MyIdInvalidFmt);
+//! #
+//! const WEB: MyId = MyId::new_const("-web"); // Compile error
+//! // error: `-web` is not a valid `MyId`: invalid first character `-` at column 1.
+//! ```
 //!
 //! ## Lifetime-Parameterized ID Types
 //!
@@ -98,140 +190,1157 @@
 //! id_newtype::id_newtype!(
 //!     MyId,           // Name of the ID type
 //!     MyIdInvalidFmt, // Name of the invalid value error
-//!     my_id,          // Name of the proc macro
+//!     my_id,          // Name of the compile time checked macro
 //!     's              // Lifetime parameter
 //! );
 //! ```
 //!
+//! ## Storage Types
+//!
+//! The ID may be stored in another type by passing it after the ID type's
+//! name, e.g. `Arc<str>` so that clones do not allocate. [`IdStorage`] is
+//! implemented for `Cow<'static, str>`, `Arc<str>`, `Rc<str>`, `Box<str>`,
+//! `String`, and `&'static str`:
+//!
+//! ```rust
+//! #[macro_use]
+//! extern crate id_newtype;
+//!
+//! use std::sync::Arc;
+//!
+//! #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+//! pub struct MyId(Arc<str>);
+//!
+//! id_newtype::id_newtype!(
+//!     MyId(Arc<str>), // Name of the ID type, and its storage type
+//!     MyIdInvalidFmt, // Name of the invalid value error
+//!     my_id           // Name of the compile time checked macro
+//! );
+//!
+//! # fn main() {
+//! let web_server = my_id!("web_server");
+//! let inner: Arc<str> = web_server.into_inner();
+//! # assert_eq!("web_server", &*inner);
+//! # }
+//! ```
+//!
+//! Only `Cow<'static, str>` can be constructed in a `const`, so `new_const` is
+//! only generated for it, and IDs with other storage types cannot be declared
+//! with `declare_ids!`. `&'static str` IDs intern the string when they are
+//! created from a `String` or `&str` at runtime, so each distinct string is
+//! leaked once.
+//!
+//! ## Generic IDs
+//!
+//! Instead of declaring a type for each kind of ID, the generic [`Id`] type may
+//! be used with a marker type for each kind. `id_kind!` declares the marker as
+//! an [`IdKind`], and takes the same options as `id_newtype!`, except
+//! `predicate`:
+//!
+//! ```rust
+//! use id_newtype::{Id, InvalidId};
+//!
+//! id_newtype::id_kind!(pub User);
+//! id_newtype::id_kind!(pub K8sName; first = "a-z", rest = "a-z0-9-");
+//!
+//! const ALICE: Id<'static, User> = Id::new_const("alice");
+//!
+//! let web_server: Id<'_, K8sName> = Id::new("web-server").unwrap();
+//! let error: InvalidId<'_, K8sName> = Id::<K8sName>::new("Web").unwrap_err();
+//! # assert_eq!("alice", ALICE.as_str());
+//! # assert_eq!("web-server", web_server.as_str());
+//! ```
+//!
+//! `Id<User>` and `Id<K8sName>` are distinct types, with the same API as
+//! lifetime-parameterized ID types, and [`InvalidId`] as their error type.
+//!
+//! ## ID Paths
+//!
+//! [`IdPath`] is a hierarchical ID such as `us-east.cluster-1.node-1`, where
+//! each segment is validated as an ID type. The separator defaults to `.`, and
+//! must not be allowed in the segment type:
+//!
+//! ```rust
+//! use std::borrow::Cow;
+//!
+//! use id_newtype::IdPath;
+//!
+//! #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+//! pub struct K8sName(Cow<'static, str>);
+//!
+//! id_newtype::id_newtype!(K8sName, K8sNameInvalidFmt; first = "a-z", rest = "a-z0-9-");
+//!
+//! let node: IdPath<K8sName> = "us-east.cluster-1.node-1".parse().unwrap();
+//! let files: IdPath<K8sName, '/'> = "etc/nginx".parse().unwrap();
+//!
+//! assert_eq!(Some("us-east.cluster-1".parse().unwrap()), node.parent());
+//! # assert_eq!("etc/nginx", files.to_string());
+//! ```
+//!
+//! With the `"macros"` feature, `id_path!` checks each segment with the
+//! segment type's `validate` at compile time, e.g. `id_path!(K8sName,
+//! "etc/nginx"; separator = '/')`.
+//!
+//! ```rust,ignore
+//! # use std::borrow::Cow;
+//! #
+//! # #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+//! # pub struct K8sName(Cow<'static, str>);
+//! #
+//! # id_newtype::id_newtype!(K8sName, K8sNameInvalidFmt; first = "a-z", rest = "a-z0-9-");
+//! #
+//! let node = id_newtype::id_path!(K8sName, "Bad_Seg.x"); // Compile error
+//! // error: `Bad_Seg` is not a valid `K8sName`: invalid first character `B` at column 1.
+//! ```
+//!
+//! ## Qualified IDs
+//!
+//! [`Qualified`] is an ID qualified by a namespace, such as `my_plugin::item`,
+//! where the namespace and local parts may be different ID types. Maps keyed by
+//! `Qualified` IDs may be queried with `(namespace, local)` pairs through
+//! [`QualifiedKey`], and `qualified!` checks both parts at compile time:
+//!
+//! ```rust
+//! use std::borrow::Cow;
+//!
+//! use id_newtype::{Qualified, QualifiedKey};
+//!
+//! #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+//! pub struct PluginId(Cow<'static, str>);
+//! id_newtype::id_newtype!(PluginId, PluginIdInvalidFmt);
+//!
+//! #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+//! pub struct ItemId(Cow<'static, str>);
+//! id_newtype::id_newtype!(ItemId, ItemIdInvalidFmt);
+//!
+//! let item: Qualified<PluginId, ItemId> = "my_plugin::item".parse().unwrap();
+//! let items = std::collections::HashSet::from([item]);
+//!
+//! let key = ("my_plugin", "item");
+//! assert!(items.contains(&key as &dyn QualifiedKey));
+//!
+//! let item = id_newtype::qualified!(PluginId, ItemId, "my_plugin::item");
+//! # assert!(items.contains(&item));
+//! ```
+//!
+//! The delimiter defaults to `::`, and other delimiters are declared with
+//! [`Delimiter`].
+//!
+//! ## ID Generators
+//!
+//! [`IdGenerator`] produces fresh IDs of any ID type, either sequentially with
+//! a prefix, randomly from `[A-Za-z0-9_]`, or sorted by time like ULIDs. The
+//! constructors check that the strategy fits the ID type, and `next_id` returns
+//! an error once the generator is exhausted:
+//!
+//! ```rust
+//! # use std::borrow::Cow;
+//! #
+//! use id_newtype::IdGenerator;
+//!
+//! # #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+//! # pub struct MyId(Cow<'static, str>);
+//! # id_newtype::id_newtype!(MyId, MyIdInvalidFmt);
+//! #
+//! # fn main() -> Result<(), id_newtype::IdGeneratorError<MyIdInvalidFmt<'static>>> {
+//! let mut items = IdGenerator::<MyId>::sequential("item_")?;
+//! let mut tokens = IdGenerator::<MyId>::random(16)?;
+//! let mut events = IdGenerator::<MyId>::time_sortable("evt_")?;
+//!
+//! let item_id: MyId = items.next_id()?; // item_1
+//! # assert_eq!("item_1", item_id.as_str());
+//! # assert_eq!(16, tokens.next_id()?.as_str().len());
+//! # assert_eq!(30, events.next_id()?.as_str().len());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Custom Grammar
+//!
+//! By default, IDs must match `[A-Za-z_][A-Za-z0-9_]*`. Options may be passed
+//! after a `;` to change the grammar:
+//!
+//! * `first = "a-z"`: Characters that an ID may begin with.
+//! * `rest = "a-z0-9-"`: Characters that an ID may contain after the first
+//!   character.
+//! * `min_len = 2`, `max_len = 63`: Minimum and maximum length of an ID,
+//!   checked before any characters are scanned or copied.
+//! * `reserved = ["internal"]`: Words that an ID must not be.
+//! * `reserved_prefixes = ["__"]`: Prefixes that an ID must not begin with.
+//! * `reserved_sets = [Rust, Sql]`: Built-in [`ReservedSet`]s of words that an
+//!   ID must not be.
+//! * `style = Snake`: A [`CaseStyle`] that IDs must be written in, which also
+//!   sets `first` and `rest`. `Snake` and `ScreamingSnake` IDs must not contain
+//!   repeated or trailing underscores. See [`IdRules::style`].
+//! * `predicate = crate::ids::my_predicate`: A `const fn(&str) -> bool` that
+//!   IDs must additionally satisfy.
+//!
+//! Character classes are written like the inside of a regex character class.
+//! The options are applied by `is_valid_id`, `TryFrom`, `FromStr`, and the
+//! generated compile time checked macro, and are described in the error
+//! message.
+//!
+//! ```rust
+//! use std::borrow::Cow;
+//!
+//! #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+//! pub struct K8sName(Cow<'static, str>);
+//!
+//! id_newtype::id_newtype!(
+//!     K8sName,
+//!     K8sNameInvalidFmt,
+//!     k8s_name;
+//!     first = "a-z",
+//!     rest = "a-z0-9-",
+//!     predicate = crate::no_trailing_hyphen,
+//! );
+//!
+//! pub const fn no_trailing_hyphen(proposed_id: &str) -> bool {
+//!     match proposed_id.as_bytes() {
+//!         [.., last] => *last != b'-',
+//!         [] => true,
+//!     }
+//! }
+//!
+//! # fn main() {
+//! assert!(K8sName::is_valid_id("web-server"));
+//! assert!(!K8sName::is_valid_id("web_server"));
+//! assert!(!K8sName::is_valid_id("web-"));
+//! # }
+//! ```
+//!
+//! The predicate must be a `const fn` so that it can be evaluated at compile
+//! time, and like the ID type, its path must resolve where the generated macro
+//! is used.
+//!
+//! `style` presets enforce a case style, and error messages name the rule
+//! that is broken:
+//!
+//! ```rust
+//! use std::borrow::Cow;
+//!
+//! #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+//! pub struct ProfileId(Cow<'static, str>);
+//!
+//! #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+//! pub struct EnvVarId(Cow<'static, str>);
+//!
+//! id_newtype::id_newtype!(ProfileId, ProfileIdInvalidFmt; style = Snake);
+//! id_newtype::id_newtype!(EnvVarId, EnvVarIdInvalidFmt; style = ScreamingSnake);
+//!
+//! assert!(ProfileId::is_valid_id("web_server_2"));
+//! assert!(EnvVarId::is_valid_id("RUST_LOG"));
+//!
+//! let error = ProfileId::new("web__x").unwrap_err();
+//! assert_eq!(
+//!     Some("`web__x` is not a valid `ProfileId`: repeated underscore `_` at column 5."),
+//!     error.to_string().lines().next()
+//! );
+//! ```
+//!
+//! ## Derive
+//!
+//! With the `"macros"` feature, `#[derive(IdNewtype)]` generates the same items
+//! as `id_newtype!`, reading the lifetime from the struct:
+//!
+//! ```rust
+//! # #[cfg(feature = "macros")]
+//! # mod ids {
+//! use std::borrow::Cow;
+//!
+//! use id_newtype::IdNewtype;
+//!
+//! #[derive(Clone, Debug, Hash, PartialEq, Eq, IdNewtype)]
+//! #[id_newtype(error = MyIdInvalidFmt, macro = my_id)]
+//! pub struct MyId<'s>(Cow<'s, str>);
+//!
+//! #[derive(Clone, Debug, Hash, PartialEq, Eq, IdNewtype)]
+//! #[id_newtype(first = "a-z", rest = "a-z0-9-")]
+//! pub struct K8sName(Cow<'static, str>);
+//! # }
+//! #
+//! # #[cfg(feature = "macros")]
+//! # fn main() {
+//! # use ids::{my_id, K8sName, MyId};
+//! #
+//! let web_server = my_id!("web_server");
+//! assert!(K8sName::is_valid_id("web-server"));
+//! # }
+//! #
+//! # #[cfg(not(feature = "macros"))]
+//! # fn main() {}
+//! ```
+//!
+//! The `error` option defaults to the struct name followed by `InvalidFmt`,
+//! and no macro is generated without the `macro` option. Other options are the
+//! grammar options above. Structs without a lifetime may use any of the
+//! storage types above.
+//!
+//! `#[derive(IdEnum)]` maps enum variants to well known IDs, with an optional
+//! variant for all other IDs. Each unit variant maps to the ID in its
+//! `#[id_enum("..")]` attribute, or its name in `snake_case`, and is checked
+//! with the ID type's `is_valid_id` at compile time:
+//!
+//! ```rust
+//! # #[cfg(feature = "macros")]
+//! # mod ids {
+//! # use std::borrow::Cow;
+//! #
+//! use id_newtype::{IdEnum, IdNewtype};
+//!
+//! # #[derive(Clone, Debug, Hash, PartialEq, Eq, IdNewtype)]
+//! # #[id_newtype(first = "a-z", rest = "a-z0-9-")]
+//! # pub struct K8sName(Cow<'static, str>);
+//! #
+//! #[derive(Clone, Debug, PartialEq, Eq, IdEnum)]
+//! #[id_enum(K8sName)]
+//! pub enum Service {
+//!     #[id_enum("web-server")]
+//!     WebServer,
+//!     Db,
+//!     Other(K8sName),
+//! }
+//! # }
+//! #
+//! # #[cfg(feature = "macros")]
+//! # fn main() {
+//! # use ids::{K8sName, Service};
+//! #
+//! let db = K8sName::new("db").unwrap();
+//! assert_eq!(Service::Db, Service::from(db));
+//! assert_eq!(
+//!     Ok(Service::WebServer),
+//!     Service::try_from(&K8sName::new("web-server").unwrap())
+//! );
+//! assert_eq!(
+//!     K8sName::new("web-server").unwrap(),
+//!     K8sName::from(Service::WebServer)
+//! );
+//! assert_eq!(&[Service::WebServer, Service::Db], Service::VARIANTS);
+//! # }
+//! #
+//! # #[cfg(not(feature = "macros"))]
+//! # fn main() {}
+//! ```
+//!
+//! `TryFrom<&K8sName>` returns the ID as the error when it is not a unit
+//! variant, and `From<K8sName>` is generated when there is a variant for other
+//! IDs. Each ID is checked with `K8sName::validate` in a `const`, so an invalid
+//! ID is a compile error with the same message as `checked_id!`.
+//!
 //! ## Features
 //!
-//! * `"macros"` This feature enables the `id!` compile-time checked proc macro
-//!   for safe construction of IDs at compile time.
+//! * `"macros"` This feature enables the `id!` and `checked_id!` compile-time
+//!   checked proc macros for safe construction of IDs at compile time.
+//!   `#[derive(IdNewtype)]` and `#[derive(IdEnum)]` are also enabled by this
+//!   feature.
 //!
 //!     ```rust
-//!     # #[cfg(feature = "my_feature")]
+//!     # #[cfg(feature = "macros")]
 //!     # {
-//!     #[macro_use]
-//!     extern crate id_newtype;
-//!     use id_newtype::id;
+//!     use id_newtype::checked_id;
 //!
-//!     // Define a new ID type
-//!     #[derive(Clone, Debug, Hash, PartialEq, Eq)]
-//!     pub struct MyId(Cow<'static, str>);
-//!     id_newtype::id_newtype!(MyId, MyIdInvalidFmt, id);
+//!     mod ids {
+//!         use std::borrow::Cow;
+//!
+//!         // Define a new ID type
+//!         #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+//!         pub struct MyId(Cow<'static, str>);
+//!         id_newtype::id_newtype!(MyId, MyIdInvalidFmt);
+//!     }
 //!
 //!     // ok!
-//!     let id = id!("my_id");
+//!     let id = checked_id!(ids::MyId, "my_id");
+//!
+//!     // `invalid id` is not a valid `MyId`:
+//!     // invalid character ` ` at column 8.
+//!     // let id = checked_id!(ids::MyId, "invalid id");
+//!     # }
+//!     ```
+//!
+//!   `ids!` and `declare_ids!` check lists of IDs at compile time with the
+//!   type's `validate`, and reject duplicates. A `declare_ids!` constant
+//!   without an ID, e.g. `pub(crate) const ALL_SERVICES;`, is the slice of
+//!   every declared ID:
+//!
+//!     ```rust
+//!     # #[cfg(feature = "macros")]
+//!     # {
+//!     use id_newtype::{declare_ids, ids};
+//!
+//!     mod ids {
+//!         use std::borrow::Cow;
+//!
+//!         #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+//!         pub struct MyId(Cow<'static, str>);
+//!         id_newtype::id_newtype!(MyId, MyIdInvalidFmt);
+//!     }
+//!
+//!     const SERVICES: [ids::MyId; 3] = ids!(ids::MyId; "web", "db", "cache");
+//!
+//!     declare_ids! {
+//!         ids::MyId;
+//!
+//!         /// The web server.
+//!         pub const WEB = "web";
+//!         pub const DB = "db";
+//!
+//!         /// Every declared service.
+//!         pub(crate) const ALL_SERVICES;
+//!     }
+//!     # assert_eq!(&SERVICES[..2], ALL_SERVICES);
+//!     # }
+//!     ```
+//!
+//! * `"serde"`: Implements `Serialize` as a string, and `Deserialize` with the
+//!   same validation as `FromStr`, reporting the invalid value error's message.
+//!   `serde` is re-exported, so crates that use `id_newtype!` do not need to
+//!   depend on it.
+//!
+//!   Lifetime-parameterized ID types borrow from the input when deserialized
+//!   with `#[serde(borrow)]`:
+//!
+//!     ```rust
+//!     # #[cfg(feature = "serde")]
+//!     # {
+//!     use std::borrow::Cow;
+//!
+//!     use serde::Deserialize;
+//!
+//!     #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+//!     pub struct MyId<'s>(Cow<'s, str>);
+//!     id_newtype::id_newtype!(MyId, MyIdInvalidFmt, my_id, 's);
 //!
-//!     // `id` is not a valid `Id`
-//!     // `Id`s must begin with a letter or underscore, and contain only
-//!     // letters, numbers, or underscores.
-//!     let id = id!("invalid id");
+//!     #[derive(Deserialize)]
+//!     struct Service<'s> {
+//!         #[serde(borrow)]
+//!         name: MyId<'s>,
+//!     }
+//!
+//!     let json = r#"{"name":"web"}"#;
+//!     let service: Service<'_> = serde_json::from_str(json).unwrap();
+//!     assert_eq!("web", service.name.as_str());
+//!
+//!     let json = r#"{"name":"a b"}"#;
+//!     assert!(serde_json::from_str::<Service<'_>>(json).is_err());
 //!     # }
 //!     ```
+//!
+//! * `"schemars"`: Implements `JsonSchema` as a `string` schema with the type's
+//!   `PATTERN`, and `minLength` and `maxLength` if configured.
+//! * `"utoipa"`: Implements `PartialSchema` and `ToSchema` with the same
+//!   schema, for OpenAPI documents.
+//!
+//!   `PATTERN` is a regex for the type's length and character rules, as
+//!   described by [`IdRules::pattern`]:
+//!
+//!     ```rust
+//!     use std::borrow::Cow;
+//!
+//!     #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+//!     pub struct K8sName(Cow<'static, str>);
+//!     id_newtype::id_newtype!(
+//!         K8sName, K8sNameInvalidFmt;
+//!         first = "a-z", rest = "a-z0-9-", max_len = 63,
+//!     );
+//!
+//!     assert_eq!("^[a-z][a-z0-9\\-]{0,62}$", K8sName::PATTERN);
+//!     ```
 
-// Re-export the compiled-time checked constructor.
+pub use crate::{
+    case_display::CaseDisplay,
+    case_style::CaseStyle,
+    char_class::CharClass,
+    delimiter::{Delimiter, DoubleColon},
+    id::Id,
+    id_decode_error::IdDecodeError,
+    id_edit::IdEdit,
+    id_generator::IdGenerator,
+    id_generator_error::IdGeneratorError,
+    id_kind::IdKind,
+    id_newtype_trait::IdNewtype,
+    id_pack_error::IdPackError,
+    id_path::IdPath,
+    id_path_error::IdPathError,
+    id_rules::IdRules,
+    id_set::IdSet,
+    id_storage::IdStorage,
+    id_violation::IdViolation,
+    intern::Intern,
+    interned::Interned,
+    interner::Interner,
+    invalid_id::InvalidId,
+    invalid_reason::InvalidReason,
+    packed_id::PackedId,
+    qualified::Qualified,
+    qualified_error::QualifiedError,
+    qualified_key::QualifiedKey,
+    reserved_set::ReservedSet,
+    uniquify_error::UniquifyError,
+    words::Words,
+};
+
+// Re-exported so that the `serde` impls generated by `id_newtype!` do not
+// require a `serde` dependency in the user's crate.
+#[cfg(feature = "schemars")]
+pub use schemars;
+#[cfg(feature = "serde")]
+pub use serde;
+#[cfg(feature = "utoipa")]
+pub use utoipa;
+
+// Re-export the compiled-time checked constructors.
 #[cfg(feature = "macros")]
-pub use id_newtype_macros::id;
+pub use id_newtype_macros::{checked_id, declare_ids, id, id_path, ids, IdEnum, IdNewtype};
+
+// Allows `#[derive(IdNewtype)]`, which refers to `::id_newtype`, in this
+// crate's tests.
+#[cfg(test)]
+extern crate self as id_newtype;
+
+mod case_display;
+mod case_style;
+mod char_class;
+mod const_str;
+mod delimiter;
+mod id;
+mod id_decode_error;
+mod id_edit;
+mod id_generator;
+mod id_generator_error;
+mod id_kind;
+mod id_newtype_trait;
+mod id_pack_error;
+mod id_path;
+mod id_path_error;
+mod id_rules;
+mod id_set;
+mod id_storage;
+mod id_violation;
+mod intern;
+mod interned;
+mod interner;
+mod invalid_id;
+mod invalid_reason;
+#[cfg(doctest)]
+mod macro_compile_fail;
+mod packed_id;
+mod qualified;
+mod qualified_error;
+mod qualified_key;
+mod reserved_set;
+mod rng;
+mod transliterate;
+mod uniquify_error;
+mod words;
+
+/// Checks the ID through `const` evaluation of the type's `validate`, used by
+/// the macros generated by `id_newtype!`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __checked_id {
+    ($ty_name:ident, $proposed_id:literal) => {{
+        const {
+            if let Err(violation) = $ty_name::validate($proposed_id) {
+                violation.panic(stringify!($ty_name), $proposed_id)
+            }
+        }
+        $ty_name::new_unchecked($proposed_id)
+    }};
+    ($ty_name:ident,) => {
+        $crate::__checked_id!($ty_name, "")
+    };
+}
+
+/// Implements `Serialize` and `Deserialize`, used by `id_newtype!` when the
+/// `"serde"` feature is enabled.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __serde_impl {
+    (IMPL; $ty_name:ident) => {
+        impl $crate::serde::Serialize for $ty_name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: $crate::serde::Serializer,
+            {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> $crate::serde::Deserialize<'de> for $ty_name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: $crate::serde::Deserializer<'de>,
+            {
+                struct IdVisitor;
+
+                impl<'de> $crate::serde::de::Visitor<'de> for IdVisitor {
+                    type Value = $ty_name;
+
+                    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                        f.write_str(concat!("a `", stringify!($ty_name), "` string"))
+                    }
+
+                    fn visit_str<E>(self, s: &str) -> Result<$ty_name, E>
+                    where
+                        E: $crate::serde::de::Error,
+                    {
+                        s.parse().map_err(E::custom)
+                    }
+
+                    fn visit_string<E>(self, s: String) -> Result<$ty_name, E>
+                    where
+                        E: $crate::serde::de::Error,
+                    {
+                        $ty_name::try_from(s).map_err(E::custom)
+                    }
+                }
+
+                deserializer.deserialize_string(IdVisitor)
+            }
+        }
+    };
+    (IMPL_LT; $ty_name:ident, $lt:lifetime) => {
+        impl<$lt> $crate::serde::Serialize for $ty_name<$lt> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: $crate::serde::Serializer,
+            {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        // Borrowed strings are deserialized as `Cow::Borrowed` without copying.
+        impl<'de: $lt, $lt> $crate::serde::Deserialize<'de> for $ty_name<$lt> {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: $crate::serde::Deserializer<'de>,
+            {
+                struct IdVisitor<$lt>(std::marker::PhantomData<$ty_name<$lt>>);
+
+                impl<'de: $lt, $lt> $crate::serde::de::Visitor<'de> for IdVisitor<$lt> {
+                    type Value = $ty_name<$lt>;
+
+                    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                        f.write_str(concat!("a `", stringify!($ty_name), "` string"))
+                    }
+
+                    fn visit_borrowed_str<E>(self, s: &'de str) -> Result<$ty_name<$lt>, E>
+                    where
+                        E: $crate::serde::de::Error,
+                    {
+                        $ty_name::try_from(s).map_err(E::custom)
+                    }
+
+                    fn visit_str<E>(self, s: &str) -> Result<$ty_name<$lt>, E>
+                    where
+                        E: $crate::serde::de::Error,
+                    {
+                        s.parse().map_err(E::custom)
+                    }
+
+                    fn visit_string<E>(self, s: String) -> Result<$ty_name<$lt>, E>
+                    where
+                        E: $crate::serde::de::Error,
+                    {
+                        $ty_name::try_from(s).map_err(E::custom)
+                    }
+                }
+
+                deserializer.deserialize_str(IdVisitor(std::marker::PhantomData))
+            }
+        }
+    };
+}
+
+/// Expands to nothing, as the `"serde"` feature is disabled.
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __serde_impl {
+    ($($tt:tt)*) => {};
+}
+
+/// Implements `JsonSchema`, used by `id_newtype!` when the `"schemars"` feature
+/// is enabled.
+#[cfg(feature = "schemars")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __schemars_impl {
+    (IMPL; $ty_name:ident) => {
+        impl $crate::schemars::JsonSchema for $ty_name {
+            $crate::__schemars_impl!(FNS; $ty_name);
+        }
+    };
+    (IMPL_LT; $ty_name:ident, $lt:lifetime) => {
+        impl<$lt> $crate::schemars::JsonSchema for $ty_name<$lt> {
+            $crate::__schemars_impl!(FNS; $ty_name);
+        }
+    };
+    (FNS; $ty_name:ident) => {
+        fn schema_name() -> std::borrow::Cow<'static, str> {
+            std::borrow::Cow::Borrowed(stringify!($ty_name))
+        }
+
+        fn schema_id() -> std::borrow::Cow<'static, str> {
+            std::borrow::Cow::Borrowed(concat!(module_path!(), "::", stringify!($ty_name)))
+        }
+
+        fn json_schema(
+            _generator: &mut $crate::schemars::SchemaGenerator,
+        ) -> $crate::schemars::Schema {
+            let mut schema = $crate::schemars::json_schema!({
+                "type": "string",
+                "pattern": Self::PATTERN,
+            });
+            if let Some(min_len) = Self::RULES.min_len_limit() {
+                schema.insert(String::from("minLength"), min_len.into());
+            }
+            if let Some(max_len) = Self::RULES.max_len_limit() {
+                schema.insert(String::from("maxLength"), max_len.into());
+            }
+            schema
+        }
+    };
+}
+
+/// Expands to nothing, as the `"schemars"` feature is disabled.
+#[cfg(not(feature = "schemars"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __schemars_impl {
+    ($($tt:tt)*) => {};
+}
+
+/// Implements `PartialSchema` and `ToSchema`, used by `id_newtype!` when the
+/// `"utoipa"` feature is enabled.
+#[cfg(feature = "utoipa")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __utoipa_impl {
+    (IMPL; $ty_name:ident) => {
+        impl $crate::utoipa::PartialSchema for $ty_name {
+            $crate::__utoipa_impl!(SCHEMA_FN);
+        }
+
+        impl $crate::utoipa::ToSchema for $ty_name {
+            $crate::__utoipa_impl!(NAME_FN; $ty_name);
+        }
+    };
+    (IMPL_LT; $ty_name:ident, $lt:lifetime) => {
+        impl<$lt> $crate::utoipa::PartialSchema for $ty_name<$lt> {
+            $crate::__utoipa_impl!(SCHEMA_FN);
+        }
+
+        impl<$lt> $crate::utoipa::ToSchema for $ty_name<$lt> {
+            $crate::__utoipa_impl!(NAME_FN; $ty_name);
+        }
+    };
+    (SCHEMA_FN) => {
+        fn schema() -> $crate::utoipa::openapi::RefOr<$crate::utoipa::openapi::schema::Schema> {
+            $crate::utoipa::openapi::schema::ObjectBuilder::new()
+                .schema_type($crate::utoipa::openapi::schema::Type::String)
+                .pattern(Some(Self::PATTERN))
+                .min_length(Self::RULES.min_len_limit())
+                .max_length(Self::RULES.max_len_limit())
+                .into()
+        }
+    };
+    (NAME_FN; $ty_name:ident) => {
+        fn name() -> std::borrow::Cow<'static, str> {
+            std::borrow::Cow::Borrowed(stringify!($ty_name))
+        }
+    };
+}
+
+/// Expands to nothing, as the `"utoipa"` feature is disabled.
+#[cfg(not(feature = "utoipa"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __utoipa_impl {
+    ($($tt:tt)*) => {};
+}
+
+/// Declares an uninhabited enum as an [`IdKind`] for the generic [`Id`] type.
+///
+/// The options are the same as for `id_newtype!`, except `predicate`, which is
+/// a compile error when the kind is used.
+///
+/// ```rust
+/// use id_newtype::Id;
+///
+/// id_newtype::id_kind!(
+///     /// Kind of IDs for Kubernetes objects.
+///     pub K8sName;
+///     first = "a-z",
+///     rest = "a-z0-9-",
+///     max_len = 63,
+/// );
+///
+/// assert!(Id::<K8sName>::new("web-server").is_ok());
+/// ```
+#[macro_export]
+macro_rules! id_kind {
+    ($(#[$attr:meta])* $vis:vis $kind:ident $(; $($opts:tt)*)?) => {
+        $(#[$attr])*
+        $vis enum $kind {}
+
+        impl $crate::IdKind for $kind {
+            const TYPE_NAME: &'static str = stringify!($kind);
+            const RULES: $crate::IdRules = {
+                let rules = $crate::id_newtype!(RULES; [] $($($opts)*)?);
+                assert!(
+                    rules.predicate_name().is_none(),
+                    "`id_kind!` does not support `predicate`, as `Id`s are validated in `const` functions."
+                );
+                rules
+            };
+        }
+    };
+}
+
+/// Returns a [`Qualified`] ID, with both parts validated at compile time.
+///
+/// The arguments are the namespace and local ID types, and the value. The
+/// delimiter defaults to `::`, and may be set with a `delimiter = Type`
+/// option. Both parts are checked through `const` evaluation, so this works
+/// for types declared with `id_newtype!`, and for [`Id`]s.
+///
+/// ```rust
+/// use std::borrow::Cow;
+///
+/// use id_newtype::Qualified;
+///
+/// #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// pub struct PluginId(Cow<'static, str>);
+/// id_newtype::id_newtype!(PluginId, PluginIdInvalidFmt);
+///
+/// #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// pub struct ItemId(Cow<'static, str>);
+/// id_newtype::id_newtype!(ItemId, ItemIdInvalidFmt);
+///
+/// let item = id_newtype::qualified!(PluginId, ItemId, "my_plugin::item");
+/// # assert_eq!("my_plugin::item", item.to_string());
+/// ```
+///
+/// If either part is invalid, a compilation error is produced:
+///
+/// ```rust,compile_fail,E0080
+/// # use std::borrow::Cow;
+/// #
+/// # #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// # pub struct PluginId(Cow<'static, str>);
+/// # id_newtype::id_newtype!(PluginId, PluginIdInvalidFmt);
+/// #
+/// # #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// # pub struct ItemId(Cow<'static, str>);
+/// # id_newtype::id_newtype!(ItemId, ItemIdInvalidFmt);
+/// #
+/// // error: `my plugin` is not a valid `PluginId`: invalid character ` ` at column 3.
+/// let item = id_newtype::qualified!(PluginId, ItemId, "my plugin::item");
+/// ```
+#[macro_export]
+macro_rules! qualified {
+    ($ns:ty, $local:ty, $value:literal $(;)?) => {
+        $crate::qualified!($ns, $local, $value; delimiter = $crate::DoubleColon)
+    };
+    ($ns:ty, $local:ty, $value:literal; delimiter = $delimiter:ty $(,)?) => {{
+        const PARTS: (&str, &str) =
+            match $crate::Qualified::<$ns, $local, $delimiter>::split($value) {
+                Some(parts) => parts,
+                None => panic!(
+                    "{}",
+                    concat!("`", $value, "` is not a qualified ID, as it does not contain the delimiter.")
+                ),
+            };
+        const {
+            if let Err(violation) = <$ns>::validate(PARTS.0) {
+                violation.panic(stringify!($ns), PARTS.0)
+            }
+            if let Err(violation) = <$local>::validate(PARTS.1) {
+                violation.panic(stringify!($local), PARTS.1)
+            }
+        }
+        $crate::Qualified::<$ns, $local, $delimiter>::from_parts(
+            <$ns>::new_unchecked(PARTS.0),
+            <$local>::new_unchecked(PARTS.1),
+        )
+    }};
+}
 
 #[macro_export]
 macro_rules! id_newtype {
     // No macro name, no lifetime
-    ($ty_name:ident, $ty_err_name:ident) => {
+    ($ty_name:ident, $ty_err_name:ident $(; $($opts:tt)*)?) => {
+        $crate::id_newtype!(NEW; $ty_name, $ty_err_name, []);
+        $crate::id_newtype!(IMPL; $ty_name, $ty_err_name, std::borrow::Cow<'static, str>, [$($($opts)*)?]);
+    };
+
+    // With macro name, no lifetime
+    ($ty_name:ident, $ty_err_name:ident, $macro_name:ident $(; $($opts:tt)*)?) => {
+        $crate::id_newtype!(NEW; $ty_name, $ty_err_name, [$macro_name]);
+        $crate::id_newtype!(IMPL; $ty_name, $ty_err_name, std::borrow::Cow<'static, str>, [$($($opts)*)?]);
+        $crate::id_newtype!(MACRO; ($) $ty_name, $macro_name);
+    };
+
+    // Storage type, no macro name
+    ($ty_name:ident($storage:ty), $ty_err_name:ident $(; $($opts:tt)*)?) => {
+        $crate::id_newtype!(NEW_STORAGE; $ty_name, $ty_err_name, $storage, []);
+        $crate::id_newtype!(IMPL; $ty_name, $ty_err_name, $storage, [$($($opts)*)?]);
+    };
+
+    // Storage type, with macro name
+    ($ty_name:ident($storage:ty), $ty_err_name:ident, $macro_name:ident $(; $($opts:tt)*)?) => {
+        $crate::id_newtype!(NEW_STORAGE; $ty_name, $ty_err_name, $storage, [$macro_name]);
+        $crate::id_newtype!(IMPL; $ty_name, $ty_err_name, $storage, [$($($opts)*)?]);
+        $crate::id_newtype!(MACRO; ($) $ty_name, $macro_name);
+    };
+
+    // With macro name and lifetime parameter (new)
+    ($ty_name:ident, $ty_err_name:ident, $macro_name:ident, $lt:lifetime $(; $($opts:tt)*)?) => {
+        $crate::id_newtype!(NEW_LT; $ty_name, $ty_err_name, $lt, [$macro_name]);
+        $crate::id_newtype!(IMPL_LT; $ty_name, $ty_err_name, $lt, [$($($opts)*)?]);
+        $crate::id_newtype!(MACRO; ($) $ty_name, $macro_name);
+    };
+
+    // Constructors for static lifetime types
+    (NEW; $ty_name:ident, $ty_err_name:ident, [$($macro_name:ident)?]) => {
         impl $ty_name {
             #[doc = concat!("Returns a new `", stringify!($ty_name), "` if the given `&str` is valid.")]
-            ///
-            #[doc = concat!("Most users should use the `", stringify!($macro_name), "!` macro as this provides")]
-            /// compile time checks and returns a `const` value.
+            $(
+                ///
+                #[doc = concat!("Most users should use the `", stringify!($macro_name), "!` macro as this provides")]
+                /// compile time checks and returns a `const` value.
+            )?
             pub fn new(s: &'static str) -> Result<Self, $ty_err_name<'static>> {
                 Self::try_from(s)
             }
 
             #[doc = concat!("Returns a new `", stringify!($ty_name), "` without verification.")]
             ///
-            #[doc = concat!("Most users should use the `", stringify!($macro_name), "!` macro as this provides")]
-            /// compile time checks and returns a `const` value.
-            ///
+            $(
+                #[doc = concat!("Most users should use the `", stringify!($macro_name), "!` macro as this provides")]
+                /// compile time checks and returns a `const` value.
+                ///
+            )?
             /// This is here for guaranteed valid usage such as being called from the macro.
             #[doc(hidden)]
             pub const fn new_unchecked(s: &'static str) -> Self {
                 Self(std::borrow::Cow::Borrowed(s))
             }
-        }
 
-        id_newtype!(IMPL; $ty_name, $ty_err_name);
+            #[doc = concat!("Returns a new `", stringify!($ty_name), "`, checked when evaluated in a `const` context.")]
+            ///
+            /// # Panics
+            ///
+            /// Panics if the given `&str` is not valid. When used in a `const`,
+            /// this is a compile error.
+            #[track_caller]
+            pub const fn new_const(s: &'static str) -> Self {
+                match Self::validate(s) {
+                    Ok(()) => Self::new_unchecked(s),
+                    Err(violation) => violation.panic(stringify!($ty_name), s),
+                }
+            }
+        }
     };
 
-    // With macro name, no lifetime
-    ($ty_name:ident, $ty_err_name:ident, $macro_name:ident) => {
+    // Constructors for static lifetime types with a storage type
+    (NEW_STORAGE; $ty_name:ident, $ty_err_name:ident, $storage:ty, [$($macro_name:ident)?]) => {
         impl $ty_name {
             #[doc = concat!("Returns a new `", stringify!($ty_name), "` if the given `&str` is valid.")]
-            ///
-            #[doc = concat!("Most users should use the `", stringify!($macro_name), "!` macro as this provides")]
-            /// compile time checks and returns a `const` value.
+            $(
+                ///
+                #[doc = concat!("Most users should use the `", stringify!($macro_name), "!` macro as this provides")]
+                /// compile time checks.
+            )?
             pub fn new(s: &'static str) -> Result<Self, $ty_err_name<'static>> {
                 Self::try_from(s)
             }
 
-            #[doc = concat!("Returns a new `", stringify!($ty_name), "` without verification.")]
+            #[doc = concat!("Returns a new `", stringify!($ty_name), "` without verification.")]
+            ///
+            $(
+                #[doc = concat!("Most users should use the `", stringify!($macro_name), "!` macro as this provides")]
+                /// compile time checks.
+                ///
+            )?
+            /// This is here for guaranteed valid usage such as being called from the macro.
+            #[doc(hidden)]
+            pub fn new_unchecked(s: &'static str) -> Self {
+                Self(<$storage as $crate::IdStorage>::from_static(s))
+            }
+        }
+    };
+
+    // Constructors for lifetime-parameterized types
+    (NEW_LT; $ty_name:ident, $ty_err_name:ident, $lt:lifetime, [$($macro_name:ident)?]) => {
+        impl<$lt> $ty_name<$lt> {
+            #[doc = concat!("Returns a new `", stringify!($ty_name), "` if the given `&str` is valid.")]
+            $(
+                ///
+                #[doc = concat!("Most users should use the `", stringify!($macro_name), "!` macro as this provides")]
+                /// compile time checks and returns a `const` value.
+            )?
+            pub fn new(s: &$lt str) -> Result<Self, $ty_err_name<$lt>> {
+                Self::try_from(s)
+            }
+
+            #[doc = concat!("Returns a new `", stringify!($ty_name), "` without verification.")]
+            ///
+            $(
+                #[doc = concat!("Most users should use the `", stringify!($macro_name), "!` macro as this provides")]
+                /// compile time checks and returns a `const` value.
+                ///
+            )?
+            /// This is here for guaranteed valid usage such as being called from the macro.
+            #[doc(hidden)]
+            pub const fn new_unchecked(s: &$lt str) -> $ty_name<$lt> {
+                $ty_name(std::borrow::Cow::Borrowed(s))
+            }
+        }
+    };
+
+    // Compile time checked constructor macro.
+    //
+    // `$d` is the `$` token, which cannot be written directly in the nested
+    // `macro_rules!` definition.
+    //
+    // The macro expands to the bare `$ty_name`, as the module path of the type
+    // is not known here, so the type must be in scope where it is used.
+    (MACRO; ($d:tt) $ty_name:ident, $macro_name:ident) => {
+        #[doc = concat!("Returns a `", stringify!($ty_name), "` validated at compile time.")]
+        ///
+        #[doc = concat!("`", stringify!($ty_name), "` must be in scope where this macro is used.")]
+        /// This macro is crate-local, and cannot be used by other crates.
+        #[allow(unused_macros)]
+        macro_rules! $macro_name {
+            ($d ($d proposed_id:literal)?) => {
+                $crate::__checked_id!($ty_name, $d ($d proposed_id)?)
+            };
+        }
+
+        #[allow(unused_imports)]
+        pub(crate) use $macro_name;
+    };
+
+    // Builds the `IdRules` from the options.
+    (RULES; [$($built:tt)*]) => {
+        $crate::IdRules::new() $($built)*
+    };
+    (RULES; [$($built:tt)*] predicate = $predicate:path $(, $($opts:tt)*)?) => {
+        $crate::id_newtype!(
+            RULES;
+            [$($built)* .with_predicate_name(stringify!($predicate))]
+            $($($opts)*)?
+        )
+    };
+    (RULES; [$($built:tt)*] reserved_sets = [$($reserved_set:ident),* $(,)?] $(, $($opts:tt)*)?) => {
+        $crate::id_newtype!(
+            RULES;
+            [$($built)* .reserved_sets(&[$($crate::ReservedSet::$reserved_set),*])]
+            $($($opts)*)?
+        )
+    };
+    (RULES; [$($built:tt)*] style = $style:ident $(, $($opts:tt)*)?) => {
+        $crate::id_newtype!(RULES; [$($built)* .style($crate::CaseStyle::$style)] $($($opts)*)?)
+    };
+    (RULES; [$($built:tt)*] $key:ident = [$($value:expr),* $(,)?] $(, $($opts:tt)*)?) => {
+        $crate::id_newtype!(RULES; [$($built)* .$key(&[$($value),*])] $($($opts)*)?)
+    };
+    (RULES; [$($built:tt)*] $key:ident = $value:expr $(, $($opts:tt)*)?) => {
+        $crate::id_newtype!(RULES; [$($built)* .$key($value)] $($($opts)*)?)
+    };
+
+    // Builds the regex pattern from the options in a `const`.
+    (PATTERN; $($opts:tt)*) => {{
+        const RULES: $crate::IdRules = $crate::id_newtype!(RULES; [] $($opts)*);
+        const BYTES: [u8; RULES.pattern_len()] = RULES.pattern_bytes();
+        match std::str::from_utf8(&BYTES) {
+            Ok(pattern) => pattern,
+            Err(_) => panic!("Patterns only contain ASCII characters."),
+        }
+    }};
+
+    // Calls the predicate from the options, if any.
+    (PREDICATE; $proposed_id:ident;) => {
+        true
+    };
+    (PREDICATE; $proposed_id:ident; predicate = $predicate:path $(, $($opts:tt)*)?) => {
+        $predicate($proposed_id)
+    };
+    (PREDICATE; $proposed_id:ident; $key:ident = $value:expr $(, $($opts:tt)*)?) => {
+        $crate::id_newtype!(PREDICATE; $proposed_id; $($($opts)*)?)
+    };
+
+    // Implementation for static lifetime types
+    (IMPL; $ty_name:ident, $ty_err_name:ident, $storage:ty, [$($opts:tt)*]) => {
+        impl $ty_name {
+            #[doc = concat!("Rules that a valid `", stringify!($ty_name), "` must satisfy.")]
+            pub const RULES: $crate::IdRules = $crate::id_newtype!(RULES; [] $($opts)*);
+
+            #[doc = concat!("Regex that matches valid `", stringify!($ty_name), "`s, e.g. for JSON Schema and OpenAPI documents.")]
+            ///
+            /// Reserved words, reserved prefixes, the case style's underscore
+            /// rules, and the predicate are not expressed in the pattern. See `IdRules::pattern` for details.
+            pub const PATTERN: &'static str = $crate::id_newtype!(PATTERN; $($opts)*);
+
+            #[doc = concat!("Returns whether the provided `&str` is a valid `", stringify!($ty_name), "`.")]
+            pub const fn is_valid_id(proposed_id: &str) -> bool {
+                Self::validate(proposed_id).is_ok()
+            }
+
+            #[doc = concat!("Returns the first violation of `", stringify!($ty_name), "`'s rules in the provided `&str`, if any.")]
+            pub const fn validate(proposed_id: &str) -> Result<(), $crate::IdViolation> {
+                if let Err(violation) = Self::RULES.validate(proposed_id) {
+                    return Err(violation);
+                }
+                if $crate::id_newtype!(PREDICATE; proposed_id; $($opts)*) {
+                    Ok(())
+                } else {
+                    Err($crate::IdViolation::new($crate::InvalidReason::Predicate, 0, None))
+                }
+            }
+
+            #[doc = concat!("Returns a `", stringify!($ty_name), "` built from arbitrary text, replacing or removing characters that are not allowed.")]
             ///
-            #[doc = concat!("Most users should use the `", stringify!($macro_name), "!` macro as this provides")]
-            /// compile time checks and returns a `const` value.
+            /// See `IdRules::sanitize` for how the text is changed. If the
+            /// result is rejected by the predicate, the fixed fallback value
+            /// `RULES.sanitize("")` is returned instead.
             ///
-            /// This is here for guaranteed valid usage such as being called from the macro.
-            #[doc(hidden)]
-            pub const fn new_unchecked(s: &'static str) -> Self {
-                Self(std::borrow::Cow::Borrowed(s))
-            }
-        }
-
-        id_newtype!(IMPL; $ty_name, $ty_err_name);
-    };
-
-    // With macro name and lifetime parameter (new)
-    ($ty_name:ident, $ty_err_name:ident, $macro_name:ident, $lt:lifetime) => {
-        impl<$lt> $ty_name<$lt> {
-            #[doc = concat!("Returns a new `", stringify!($ty_name), "` if the given `&str` is valid.")]
+            /// # Panics
             ///
-            #[doc = concat!("Most users should use the `", stringify!($macro_name), "!` macro as this provides")]
-            /// compile time checks and returns a `const` value.
-            pub fn new(s: &$lt str) -> Result<Self, $ty_err_name<$lt>> {
-                Self::try_from(s)
+            /// Panics if the predicate also rejects the fallback value.
+            #[track_caller]
+            pub fn from_lossy(s: &str) -> Self {
+                let sanitized = Self::RULES.sanitize_valid(s, stringify!($ty_name), |id| {
+                    Self::validate(id).is_ok()
+                });
+                $ty_name(<$storage as $crate::IdStorage>::from_string(sanitized))
             }
 
-            #[doc = concat!("Returns a new `", stringify!($ty_name), "` without verification.")]
+            #[doc = concat!("Returns a `", stringify!($ty_name), "` that losslessly encodes arbitrary text, escaping characters that are not allowed.")]
             ///
-            #[doc = concat!("Most users should use the `", stringify!($macro_name), "!` macro as this provides")]
-            /// compile time checks and returns a `const` value.
+            /// Distinct values always have distinct encodings, and `decode`
+            /// returns the original value. See `IdRules::encode` for the
+            /// escape scheme.
             ///
-            /// This is here for guaranteed valid usage such as being called from the macro.
-            #[doc(hidden)]
-            pub const fn new_unchecked(s: &$lt str) -> $ty_name<$lt> {
-                $ty_name(std::borrow::Cow::Borrowed(s))
+            /// # Errors
+            ///
+            /// Returns an error if the encoded value does not satisfy the
+            /// rules, e.g. if it is empty or too long.
+            pub fn encode(s: &str) -> Result<Self, $ty_err_name<'static>> {
+                Self::try_from(Self::RULES.encode(s))
             }
-        }
 
-        id_newtype!(IMPL_LT; $ty_name, $ty_err_name, $lt);
-    };
-
-    // Implementation for static lifetime types
-    (IMPL; $ty_name:ident, $ty_err_name:ident) => {
-        impl $ty_name {
-            /// Returns whether the provided `&str` is a valid station identifier.
-            pub fn is_valid_id(proposed_id: &str) -> bool {
-                let mut chars = proposed_id.chars();
-                let first_char = chars.next();
-                let first_char_valid = first_char
-                    .map(|c| c.is_ascii_alphabetic() || c == '_')
-                    .unwrap_or(false);
-                let remainder_chars_valid =
-                    chars.all(|c| c.is_ascii_alphabetic() || c == '_' || c.is_ascii_digit());
-
-                first_char_valid && remainder_chars_valid
+            /// Returns the original value of an ID produced by `encode`.
+            pub fn decode(&self) -> Result<String, $crate::IdDecodeError> {
+                $crate::IdRules::decode(&self.0)
             }
 
-            /// Returns the inner `Cow<'static, str>`.
-            pub fn into_inner(self) -> Cow<'static, str> {
+            #[doc = concat!("Returns the inner `", stringify!($storage), "`.")]
+            pub fn into_inner(self) -> $storage {
                 self.0
             }
 
@@ -242,7 +1351,7 @@ macro_rules! id_newtype {
         }
 
         impl std::ops::Deref for $ty_name {
-            type Target = std::borrow::Cow<'static, str>;
+            type Target = $storage;
 
             fn deref(&self) -> &Self::Target {
                 &self.0
@@ -259,11 +1368,12 @@ macro_rules! id_newtype {
             type Error = $ty_err_name<'static>;
 
             fn try_from(s: String) -> Result<$ty_name, $ty_err_name<'static>> {
-                if Self::is_valid_id(&s) {
-                    Ok($ty_name(std::borrow::Cow::Owned(s)))
-                } else {
-                    let s = std::borrow::Cow::Owned(s);
-                    Err($ty_err_name::new(s))
+                match Self::validate(&s) {
+                    Ok(()) => Ok($ty_name(<$storage as $crate::IdStorage>::from_string(s))),
+                    Err(violation) => {
+                        let s = std::borrow::Cow::Owned(s);
+                        Err($ty_err_name::new(s, violation))
+                    }
                 }
             }
         }
@@ -272,11 +1382,12 @@ macro_rules! id_newtype {
             type Error = $ty_err_name<'static>;
 
             fn try_from(s: &'static str) -> Result<$ty_name, $ty_err_name<'static>> {
-                if Self::is_valid_id(s) {
-                    Ok($ty_name(std::borrow::Cow::Borrowed(s)))
-                } else {
-                    let s = std::borrow::Cow::Borrowed(s);
-                    Err($ty_err_name::new(s))
+                match Self::validate(s) {
+                    Ok(()) => Ok($ty_name(<$storage as $crate::IdStorage>::from_static(s))),
+                    Err(violation) => {
+                        let s = std::borrow::Cow::Borrowed(s);
+                        Err($ty_err_name::new(s, violation))
+                    }
                 }
             }
         }
@@ -285,11 +1396,12 @@ macro_rules! id_newtype {
             type Err = $ty_err_name<'static>;
 
             fn from_str(s: &str) -> Result<$ty_name, $ty_err_name<'static>> {
-                if Self::is_valid_id(s) {
-                    Ok($ty_name(std::borrow::Cow::Owned(String::from(s))))
-                } else {
-                    let s = std::borrow::Cow::Owned(String::from(s));
-                    Err($ty_err_name::new(s))
+                match Self::validate(s) {
+                    Ok(()) => Ok($ty_name(<$storage as $crate::IdStorage>::from_borrowed(s))),
+                    Err(violation) => {
+                        let s = std::borrow::Cow::Owned(String::from(violation.error_value(s)));
+                        Err($ty_err_name::new(s, violation))
+                    }
                 }
             }
         }
@@ -312,34 +1424,100 @@ macro_rules! id_newtype {
             }
         }
 
+        impl $crate::Intern for $ty_name {
+            type Static = Self;
+
+            fn interner() -> &'static $crate::Interner {
+                static INTERNER: std::sync::LazyLock<$crate::Interner> =
+                    std::sync::LazyLock::new($crate::Interner::new);
+                &INTERNER
+            }
+
+            fn from_interned(s: &'static str) -> Self {
+                Self::new_unchecked(s)
+            }
+        }
+
+        impl $crate::IdNewtype for $ty_name {
+            const TYPE_NAME: &'static str = stringify!($ty_name);
+            const RULES: $crate::IdRules = $ty_name::RULES;
+            const PATTERN: &'static str = $ty_name::PATTERN;
+
+            type Error = $ty_err_name<'static>;
+            type Inner = $storage;
+
+            fn validate(proposed_id: &str) -> Result<(), $crate::IdViolation> {
+                $ty_name::validate(proposed_id)
+            }
+
+            fn try_from_str(s: &str) -> Result<Self, $ty_err_name<'static>> {
+                s.parse()
+            }
+
+            fn try_from_string(s: String) -> Result<Self, $ty_err_name<'static>> {
+                Self::try_from(s)
+            }
+
+            fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            fn into_inner(self) -> $storage {
+                self.0
+            }
+        }
+
+        $crate::__serde_impl!(IMPL; $ty_name);
+        $crate::__schemars_impl!(IMPL; $ty_name);
+        $crate::__utoipa_impl!(IMPL; $ty_name);
+
         #[doc = concat!("Error indicating `", stringify!($ty_name), "` provided is not in the correct format.")]
         #[derive(Clone, Debug, PartialEq, Eq)]
         pub struct $ty_err_name<'s> {
             /// String that was provided for the `$ty_name`.
             value: std::borrow::Cow<'s, str>,
+            /// Why and where the value is invalid.
+            violation: $crate::IdViolation,
         }
 
         impl<'s> $ty_err_name<'s> {
             #[doc = concat!("Returns a new `", stringify!($ty_err_name), "` error.")]
-            pub fn new(value: std::borrow::Cow<'s, str>) -> Self {
-                Self { value }
+            pub fn new(value: std::borrow::Cow<'s, str>, violation: $crate::IdViolation) -> Self {
+                Self { value, violation }
             }
 
             #[doc = concat!("Returns the value that failed to be parsed as a [`", stringify!($ty_name), "`].")]
+            ///
+            /// For `TooLong` errors from `FromStr`, this is truncated to the maximum length.
             pub fn value(&self) -> &std::borrow::Cow<'s, str> {
                 &self.value
             }
+
+            /// Returns why and where the value is invalid.
+            pub fn violation(&self) -> &$crate::IdViolation {
+                &self.violation
+            }
+
+            /// Returns the reason that the value is invalid.
+            pub fn reason(&self) -> $crate::InvalidReason {
+                self.violation.reason()
+            }
+
+            /// Returns the byte offset of the offending part of the value.
+            pub fn offset(&self) -> usize {
+                self.violation.offset()
+            }
+
+            /// Returns the offending character, if the error is for a single character.
+            pub fn invalid_char(&self) -> Option<char> {
+                self.violation.invalid_char()
+            }
         }
 
         impl<'s> std::fmt::Display for $ty_err_name<'s> {
             fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-                write!(
-                    f,
-                    "`{value}` is not a valid `{ty_name}`.\n\
-                    `{ty_name}`s must begin with a letter or underscore, and contain only letters, numbers, or underscores.",
-                    ty_name = stringify!($ty_name),
-                    value = self.value
-                )
+                self.violation
+                    .fmt_error(f, stringify!($ty_name), &self.value, &$ty_name::RULES)
             }
         }
 
@@ -347,19 +1525,82 @@ macro_rules! id_newtype {
     };
 
     // Implementation for lifetime-parameterized types (new)
-    (IMPL_LT; $ty_name:ident, $ty_err_name:ident, $lt:lifetime) => {
+    (IMPL_LT; $ty_name:ident, $ty_err_name:ident, $lt:lifetime, [$($opts:tt)*]) => {
         impl<$lt> $ty_name<$lt> {
-            /// Returns whether the provided `&str` is a valid station identifier.
-            pub fn is_valid_id(proposed_id: &str) -> bool {
-                let mut chars = proposed_id.chars();
-                let first_char = chars.next();
-                let first_char_valid = first_char
-                    .map(|c| c.is_ascii_alphabetic() || c == '_')
-                    .unwrap_or(false);
-                let remainder_chars_valid =
-                    chars.all(|c| c.is_ascii_alphabetic() || c == '_' || c.is_ascii_digit());
+            #[doc = concat!("Rules that a valid `", stringify!($ty_name), "` must satisfy.")]
+            pub const RULES: $crate::IdRules = $crate::id_newtype!(RULES; [] $($opts)*);
+
+            #[doc = concat!("Regex that matches valid `", stringify!($ty_name), "`s, e.g. for JSON Schema and OpenAPI documents.")]
+            ///
+            /// Reserved words, reserved prefixes, the case style's underscore
+            /// rules, and the predicate are not expressed in the pattern. See `IdRules::pattern` for details.
+            pub const PATTERN: &'static str = $crate::id_newtype!(PATTERN; $($opts)*);
+
+            #[doc = concat!("Returns whether the provided `&str` is a valid `", stringify!($ty_name), "`.")]
+            pub const fn is_valid_id(proposed_id: &str) -> bool {
+                Self::validate(proposed_id).is_ok()
+            }
+
+            #[doc = concat!("Returns the first violation of `", stringify!($ty_name), "`'s rules in the provided `&str`, if any.")]
+            pub const fn validate(proposed_id: &str) -> Result<(), $crate::IdViolation> {
+                if let Err(violation) = Self::RULES.validate(proposed_id) {
+                    return Err(violation);
+                }
+                if $crate::id_newtype!(PREDICATE; proposed_id; $($opts)*) {
+                    Ok(())
+                } else {
+                    Err($crate::IdViolation::new($crate::InvalidReason::Predicate, 0, None))
+                }
+            }
+
+            #[doc = concat!("Returns a new `", stringify!($ty_name), "`, checked when evaluated in a `const` context.")]
+            ///
+            /// # Panics
+            ///
+            /// Panics if the given `&str` is not valid. When used in a `const`,
+            /// this is a compile error.
+            #[track_caller]
+            pub const fn new_const(s: &$lt str) -> Self {
+                match Self::validate(s) {
+                    Ok(()) => Self::new_unchecked(s),
+                    Err(violation) => violation.panic(stringify!($ty_name), s),
+                }
+            }
+
+            #[doc = concat!("Returns a `", stringify!($ty_name), "` built from arbitrary text, replacing or removing characters that are not allowed.")]
+            ///
+            /// See `IdRules::sanitize` for how the text is changed. If the
+            /// result is rejected by the predicate, the fixed fallback value
+            /// `RULES.sanitize("")` is returned instead.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the predicate also rejects the fallback value.
+            #[track_caller]
+            pub fn from_lossy(s: &str) -> Self {
+                let sanitized = Self::RULES.sanitize_valid(s, stringify!($ty_name), |id| {
+                    Self::validate(id).is_ok()
+                });
+                $ty_name(std::borrow::Cow::Owned(sanitized))
+            }
+
+            #[doc = concat!("Returns a `", stringify!($ty_name), "` that losslessly encodes arbitrary text, escaping characters that are not allowed.")]
+            ///
+            /// Distinct values always have distinct encodings, and `decode`
+            /// returns the original value. See `IdRules::encode` for the
+            /// escape scheme.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if the encoded value does not satisfy the
+            /// rules, e.g. if it is empty or too long.
+            pub fn encode(s: &str) -> Result<Self, $ty_err_name<'static>> {
+                Self::try_from(Self::RULES.encode(s))
+            }
 
-                first_char_valid && remainder_chars_valid
+            /// Returns the original value of an ID produced by `encode`.
+            pub fn decode(&self) -> Result<String, $crate::IdDecodeError> {
+                $crate::IdRules::decode(&self.0)
             }
 
             #[doc = concat!("Returns the inner `Cow<'", stringify!($lt), ", str>`.")]
@@ -396,11 +1637,12 @@ macro_rules! id_newtype {
             type Error = $ty_err_name<'static>;
 
             fn try_from(s: String) -> Result<$ty_name<$lt>, $ty_err_name<'static>> {
-                if Self::is_valid_id(&s) {
-                    Ok($ty_name(std::borrow::Cow::Owned(s)))
-                } else {
-                    let s = std::borrow::Cow::Owned(s);
-                    Err($ty_err_name::new(s))
+                match Self::validate(&s) {
+                    Ok(()) => Ok($ty_name(std::borrow::Cow::Owned(s))),
+                    Err(violation) => {
+                        let s = std::borrow::Cow::Owned(s);
+                        Err($ty_err_name::new(s, violation))
+                    }
                 }
             }
         }
@@ -409,11 +1651,12 @@ macro_rules! id_newtype {
             type Error = $ty_err_name<$lt>;
 
             fn try_from(s: &$lt str) -> Result<$ty_name<$lt>, $ty_err_name<$lt>> {
-                if Self::is_valid_id(s) {
-                    Ok($ty_name(std::borrow::Cow::Borrowed(s)))
-                } else {
-                    let s = std::borrow::Cow::Borrowed(s);
-                    Err($ty_err_name::new(s))
+                match Self::validate(s) {
+                    Ok(()) => Ok($ty_name(std::borrow::Cow::Borrowed(s))),
+                    Err(violation) => {
+                        let s = std::borrow::Cow::Borrowed(s);
+                        Err($ty_err_name::new(s, violation))
+                    }
                 }
             }
         }
@@ -422,11 +1665,12 @@ macro_rules! id_newtype {
             type Err = $ty_err_name<'static>;
 
             fn from_str(s: &str) -> Result<$ty_name<$lt>, $ty_err_name<'static>> {
-                if Self::is_valid_id(s) {
-                    Ok($ty_name(std::borrow::Cow::Owned(String::from(s))))
-                } else {
-                    let s = std::borrow::Cow::Owned(String::from(s));
-                    Err($ty_err_name::new(s))
+                match Self::validate(s) {
+                    Ok(()) => Ok($ty_name(std::borrow::Cow::Owned(String::from(s)))),
+                    Err(violation) => {
+                        let s = std::borrow::Cow::Owned(String::from(violation.error_value(s)));
+                        Err($ty_err_name::new(s, violation))
+                    }
                 }
             }
         }
@@ -449,72 +1693,509 @@ macro_rules! id_newtype {
             }
         }
 
+        impl<$lt> $crate::Intern for $ty_name<$lt> {
+            type Static = $ty_name<'static>;
+
+            fn interner() -> &'static $crate::Interner {
+                static INTERNER: std::sync::LazyLock<$crate::Interner> =
+                    std::sync::LazyLock::new($crate::Interner::new);
+                &INTERNER
+            }
+
+            fn from_interned(s: &'static str) -> Self {
+                Self::new_unchecked(s)
+            }
+        }
+
+        impl<$lt> $crate::IdNewtype for $ty_name<$lt> {
+            const TYPE_NAME: &'static str = stringify!($ty_name);
+            const RULES: $crate::IdRules = $ty_name::RULES;
+            const PATTERN: &'static str = $ty_name::PATTERN;
+
+            type Error = $ty_err_name<'static>;
+            type Inner = std::borrow::Cow<$lt, str>;
+
+            fn validate(proposed_id: &str) -> Result<(), $crate::IdViolation> {
+                $ty_name::validate(proposed_id)
+            }
+
+            fn try_from_str(s: &str) -> Result<Self, $ty_err_name<'static>> {
+                s.parse()
+            }
+
+            fn try_from_string(s: String) -> Result<Self, $ty_err_name<'static>> {
+                Self::try_from(s)
+            }
+
+            fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            fn into_inner(self) -> std::borrow::Cow<$lt, str> {
+                self.0
+            }
+        }
+
+        $crate::__serde_impl!(IMPL_LT; $ty_name, $lt);
+        $crate::__schemars_impl!(IMPL_LT; $ty_name, $lt);
+        $crate::__utoipa_impl!(IMPL_LT; $ty_name, $lt);
+
         #[doc = concat!("Error indicating `", stringify!($ty_name), "` provided is not in the correct format.")]
         #[derive(Clone, Debug, PartialEq, Eq)]
         pub struct $ty_err_name<'__err> {
             /// String that was provided for the `$ty_name`.
             value: std::borrow::Cow<'__err, str>,
+            /// Why and where the value is invalid.
+            violation: $crate::IdViolation,
         }
 
         impl<'__err> $ty_err_name<'__err> {
             #[doc = concat!("Returns a new `", stringify!($ty_err_name), "` error.")]
-            pub fn new(value: std::borrow::Cow<'__err, str>) -> Self {
-                Self { value }
+            pub fn new(value: std::borrow::Cow<'__err, str>, violation: $crate::IdViolation) -> Self {
+                Self { value, violation }
             }
 
             #[doc = concat!("Returns the value that failed to be parsed as a [`", stringify!($ty_name), "`].")]
+            ///
+            /// For `TooLong` errors from `FromStr`, this is truncated to the maximum length.
             pub fn value(&self) -> &std::borrow::Cow<'__err, str> {
                 &self.value
             }
+
+            /// Returns why and where the value is invalid.
+            pub fn violation(&self) -> &$crate::IdViolation {
+                &self.violation
+            }
+
+            /// Returns the reason that the value is invalid.
+            pub fn reason(&self) -> $crate::InvalidReason {
+                self.violation.reason()
+            }
+
+            /// Returns the byte offset of the offending part of the value.
+            pub fn offset(&self) -> usize {
+                self.violation.offset()
+            }
+
+            /// Returns the offending character, if the error is for a single character.
+            pub fn invalid_char(&self) -> Option<char> {
+                self.violation.invalid_char()
+            }
+        }
+
+        impl<'__err> std::fmt::Display for $ty_err_name<'__err> {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                self.violation
+                    .fmt_error(f, stringify!($ty_name), &self.value, &$ty_name::RULES)
+            }
+        }
+
+        impl<'__err> std::error::Error for $ty_err_name<'__err> {}
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        borrow::{Borrow, Cow},
+        collections::HashSet,
+        sync::Arc,
+    };
+
+    use crate::{CaseStyle, IdNewtype, Intern, Interned, InvalidReason, UniquifyError};
+
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    pub struct MyIdType(Cow<'static, str>);
+
+    crate::id_newtype!(
+        MyIdType,           // Name of the ID type
+        MyIdTypeInvalidFmt  // Name of the invalid value error
+    );
+
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    pub struct MyIdType2(Cow<'static, str>);
+
+    crate::id_newtype!(
+        MyIdType2,           // Name of the ID type
+        MyIdType2InvalidFmt, // Name of the invalid value error
+        my_id_static_macro
+    );
+
+    // Test for lifetime-parameterized ID type
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    pub struct MyIdType3<'s>(Cow<'s, str>);
+
+    crate::id_newtype!(
+        MyIdType3,           // Name of the ID type
+        MyIdType3InvalidFmt, // Name of the invalid value error
+        my_id_lt_macro,      // Name of the proc macro
+        's                   // Lifetime parameter
+    );
+
+    // Test for custom grammar
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    pub struct K8sName(Cow<'static, str>);
+
+    crate::id_newtype!(
+        K8sName,
+        K8sNameInvalidFmt,
+        k8s_name;
+        first = "a-z",
+        rest = "a-z0-9-",
+        predicate = self::no_trailing_hyphen,
+    );
+
+    const fn no_trailing_hyphen(proposed_id: &str) -> bool {
+        match proposed_id.as_bytes() {
+            [.., last] => *last != b'-',
+            [] => true,
+        }
+    }
+
+    // Test for a predicate that sanitized values can fail
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    pub struct WordId(Cow<'static, str>);
+
+    crate::id_newtype!(
+        WordId,
+        WordIdInvalidFmt,
+        word_id;
+        predicate = self::no_digits,
+    );
+
+    const fn no_digits(proposed_id: &str) -> bool {
+        let bytes = proposed_id.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i].is_ascii_digit() {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    // Test for length limits
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    pub struct ShortId(Cow<'static, str>);
+
+    crate::id_newtype!(
+        ShortId,
+        ShortIdInvalidFmt,
+        short_id;
+        min_len = 2,
+        max_len = 8,
+    );
+
+    // Test for reserved words
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    pub struct TableName(Cow<'static, str>);
+
+    crate::id_newtype!(
+        TableName,
+        TableNameInvalidFmt,
+        table_name;
+        reserved = ["internal", "web_2"],
+        reserved_prefixes = ["__", "tmp_"],
+        reserved_sets = [Rust, Sql],
+    );
+
+    // Tests for case style presets
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    pub struct ProfileId(Cow<'static, str>);
+
+    crate::id_newtype!(ProfileId, ProfileIdInvalidFmt, profile_id; style = Snake);
+
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    pub struct EnvVarId(Cow<'static, str>);
+
+    crate::id_newtype!(EnvVarId, EnvVarIdInvalidFmt, env_var_id; style = ScreamingSnake);
+
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    pub struct ShortSnakeId(Cow<'static, str>);
+
+    crate::id_newtype!(ShortSnakeId, ShortSnakeIdInvalidFmt; style = Snake, max_len = 5);
+
+    // Tests for storage types
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    pub struct ArcId(Arc<str>);
+
+    crate::id_newtype!(ArcId(Arc<str>), ArcIdInvalidFmt, arc_id);
+
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    pub struct BoxId(Box<str>);
+
+    crate::id_newtype!(BoxId(Box<str>), BoxIdInvalidFmt);
+
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    pub struct StringId(String);
+
+    crate::id_newtype!(StringId(String), StringIdInvalidFmt; first = "a-z");
+
+    #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+    pub struct StaticStrId(&'static str);
+
+    crate::id_newtype!(StaticStrId(&'static str), StaticStrIdInvalidFmt);
+
+    // Tests for the `"serde"` feature
+    #[cfg(feature = "serde")]
+    mod serde_impls {
+        use std::borrow::Cow;
+
+        use serde::{Deserialize, Serialize};
+
+        use super::{ArcId, K8sName, MyIdType, MyIdType3};
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Service<'s> {
+            name: K8sName,
+            #[serde(borrow)]
+            alias: MyIdType3<'s>,
+        }
+
+        #[test]
+        fn serialize_as_str() {
+            let service = Service {
+                name: K8sName::new_unchecked("web-server"),
+                alias: MyIdType3::new_unchecked("web"),
+            };
+
+            assert_eq!(
+                r#"{"name":"web-server","alias":"web"}"#,
+                serde_json::to_string(&service).unwrap()
+            );
+        }
+
+        #[test]
+        fn deserialize_validates() {
+            let my_id: MyIdType = serde_json::from_str(r#""one""#).unwrap();
+            assert_eq!(MyIdType::new_unchecked("one"), my_id);
+
+            let arc_id: ArcId = serde_json::from_value(serde_json::json!("one")).unwrap();
+            assert_eq!(ArcId::new_unchecked("one"), arc_id);
+
+            let error = serde_json::from_str::<MyIdType>(r#""a b""#).unwrap_err();
+            assert_eq!(
+                "`a b` is not a valid `MyIdType`: invalid character ` ` at column 2.\n    \
+                a b\n     \
+                ^\n\
+                `MyIdType`s must begin with a letter or underscore, and contain only letters, \
+                numbers, or underscores. at line 1 column 5",
+                error.to_string()
+            );
+        }
+
+        #[test]
+        fn deserialize_borrowed_does_not_copy() {
+            let json = String::from(r#"{"name":"web-server","alias":"web"}"#);
+            let service: Service<'_> = serde_json::from_str(&json).unwrap();
+
+            assert!(matches!(service.alias.0, Cow::Borrowed("web")));
+            assert!(
+                serde_json::from_str::<Service<'_>>(r#"{"name":"web-","alias":"web"}"#).is_err()
+            );
+        }
+
+        #[test]
+        fn deserialize_escaped_is_owned() {
+            let json = String::from(r#"{"name":"web","alias":"w\u0065b"}"#);
+            let service: Service<'_> = serde_json::from_str(&json).unwrap();
+
+            assert!(matches!(service.alias.0, Cow::Owned(_)));
+            assert_eq!("web", service.alias.as_str());
+        }
+    }
+
+    // Tests for the `"schemars"` feature
+    #[cfg(feature = "schemars")]
+    mod schemars_impls {
+        use super::{K8sName, MyIdType3, ShortId};
+
+        #[test]
+        fn json_schema_has_pattern() {
+            let schema = crate::schemars::schema_for!(K8sName);
+
+            assert_eq!(
+                serde_json::json!({
+                    "$schema": "https://json-schema.org/draft/2020-12/schema",
+                    "title": "K8sName",
+                    "type": "string",
+                    "pattern": "^[a-z][a-z0-9\\-]*$",
+                }),
+                schema.to_value()
+            );
+        }
+
+        #[test]
+        fn json_schema_has_length_limits() {
+            let schema = crate::schemars::schema_for!(ShortId);
+
+            assert_eq!(Some(&serde_json::json!(2)), schema.get("minLength"));
+            assert_eq!(Some(&serde_json::json!(8)), schema.get("maxLength"));
+            assert_eq!(
+                Some(&serde_json::json!("^[A-Za-z_][A-Za-z0-9_]{1,7}$")),
+                schema.get("pattern")
+            );
+        }
+
+        #[test]
+        fn lt_json_schema() {
+            let schema = crate::schemars::schema_for!(MyIdType3<'_>);
+
+            assert_eq!(Some(&serde_json::json!("MyIdType3")), schema.get("title"));
+            assert_eq!(
+                Some(&serde_json::json!(MyIdType3::PATTERN)),
+                schema.get("pattern")
+            );
+        }
+    }
+
+    // Tests for the `"utoipa"` feature
+    #[cfg(feature = "utoipa")]
+    mod utoipa_impls {
+        use crate::utoipa::{PartialSchema, ToSchema};
+
+        use super::{K8sName, MyIdType3, ShortId};
+
+        #[test]
+        fn schema_has_pattern() {
+            assert_eq!("K8sName", K8sName::name());
+            assert_eq!(
+                serde_json::json!({
+                    "type": "string",
+                    "pattern": "^[a-z][a-z0-9\\-]*$",
+                }),
+                serde_json::to_value(K8sName::schema()).unwrap()
+            );
+        }
+
+        #[test]
+        fn schema_has_length_limits() {
+            assert_eq!(
+                serde_json::json!({
+                    "type": "string",
+                    "pattern": "^[A-Za-z_][A-Za-z0-9_]{1,7}$",
+                    "minLength": 2,
+                    "maxLength": 8,
+                }),
+                serde_json::to_value(ShortId::schema()).unwrap()
+            );
+        }
+
+        #[test]
+        fn lt_schema() {
+            assert_eq!("MyIdType3", MyIdType3::name());
+            assert_eq!(
+                serde_json::json!({
+                    "type": "string",
+                    "pattern": MyIdType3::PATTERN,
+                }),
+                serde_json::to_value(MyIdType3::schema()).unwrap()
+            );
+        }
+    }
+
+    // Tests for `#[derive(IdNewtype)]`
+    #[cfg(feature = "macros")]
+    mod derived {
+        use std::borrow::Cow;
+
+        use crate::{IdEnum, IdNewtype};
+
+        #[derive(Clone, Debug, Hash, PartialEq, Eq, IdNewtype)]
+        pub struct DerivedId(Cow<'static, str>);
+
+        #[derive(Clone, Debug, Hash, PartialEq, Eq, IdNewtype)]
+        #[id_newtype(macro = derived_arc_id)]
+        pub struct DerivedArcId(std::sync::Arc<str>);
+
+        #[derive(Clone, Debug, Hash, PartialEq, Eq, IdNewtype)]
+        #[id_newtype(error = DerivedLtIdError, macro = derived_lt_id)]
+        #[id_newtype(first = "a-z", rest = "a-z0-9-", max_len = 8)]
+        pub struct DerivedLtId<'s>(Cow<'s, str>);
+
+        #[test]
+        fn derive_new() {
+            assert_eq!(Ok(DerivedId::new_unchecked("one")), DerivedId::new("one"));
+
+            let error: DerivedIdInvalidFmt<'_> = DerivedId::new("a b").unwrap_err();
+            assert_eq!(crate::InvalidReason::InvalidChar, error.reason());
+        }
+
+        #[test]
+        fn derive_lt_options() {
+            let value = String::from("web-app");
+            let derived_lt_id = DerivedLtId::new(&value);
+            assert_eq!(Ok(DerivedLtId::new_unchecked("web-app")), derived_lt_id);
+
+            let error: DerivedLtIdError<'_> = DerivedLtId::new("web_app").unwrap_err();
+            assert_eq!(crate::InvalidReason::InvalidChar, error.reason());
+            assert!(!DerivedLtId::is_valid_id("web-server-2"));
         }
 
-        impl<'__err> std::fmt::Display for $ty_err_name<'__err> {
-            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-                write!(
-                    f,
-                    "`{value}` is not a valid `{ty_name}`.\n\
-                    `{ty_name}`s must begin with a letter or underscore, and contain only letters, numbers, or underscores.",
-                    ty_name = stringify!($ty_name),
-                    value = self.value
-                )
-            }
+        #[test]
+        fn derive_storage() {
+            let derived_arc_id = derived_arc_id!("web");
+            let inner: std::sync::Arc<str> = derived_arc_id.clone().into_inner();
+
+            assert_eq!("web", &*inner);
+            assert_eq!(Ok(derived_arc_id), "web".parse::<DerivedArcId>());
         }
 
-        impl<'__err> std::error::Error for $ty_err_name<'__err> {}
-    };
-}
+        #[test]
+        fn derive_generated_macro() {
+            let derived_lt_id = derived_lt_id!("web");
 
-#[cfg(test)]
-mod tests {
-    use std::borrow::{Borrow, Cow};
+            assert_eq!(DerivedLtId::new_unchecked("web"), derived_lt_id);
+        }
 
-    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
-    pub struct MyIdType(Cow<'static, str>);
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, IdEnum)]
+        #[id_enum(DerivedId)]
+        pub enum Role {
+            Admin,
+            ReadOnly,
+        }
 
-    crate::id_newtype!(
-        MyIdType,           // Name of the ID type
-        MyIdTypeInvalidFmt  // Name of the invalid value error
-    );
+        #[derive(Clone, Debug, PartialEq, Eq, IdEnum)]
+        #[id_enum(DerivedLtId<'s>)]
+        pub enum Service<'s> {
+            #[id_enum("web-app")]
+            WebApp,
+            Db,
+            Other(DerivedLtId<'s>),
+        }
 
-    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
-    pub struct MyIdType2(Cow<'static, str>);
+        #[test]
+        fn derive_id_enum_into_id() {
+            assert_eq!(DerivedId::new_unchecked("read_only"), Role::ReadOnly.into());
+            assert_eq!(
+                DerivedLtId::new_unchecked("web-app"),
+                Service::WebApp.into()
+            );
 
-    crate::id_newtype!(
-        MyIdType2,           // Name of the ID type
-        MyIdType2InvalidFmt, // Name of the invalid value error
-        my_id_static_macro
-    );
+            let other = DerivedLtId::new_unchecked("cache");
+            assert_eq!(other.clone(), Service::Other(other).into());
+        }
 
-    // Test for lifetime-parameterized ID type
-    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
-    pub struct MyIdType3<'s>(Cow<'s, str>);
+        #[test]
+        fn derive_id_enum_from_id() {
+            let admin = DerivedId::new_unchecked("admin");
+            let user = DerivedId::new_unchecked("user");
+            assert_eq!(Ok(Role::Admin), Role::try_from(&admin));
+            assert_eq!(Err(&user), Role::try_from(&user));
+
+            let value = String::from("cache");
+            let cache = DerivedLtId::new(&value).unwrap();
+            assert_eq!(Service::Db, Service::from(DerivedLtId::new_unchecked("db")));
+            assert_eq!(Service::Other(cache.clone()), Service::from(cache));
+        }
 
-    crate::id_newtype!(
-        MyIdType3,           // Name of the ID type
-        MyIdType3InvalidFmt, // Name of the invalid value error
-        my_id_lt_macro,      // Name of the proc macro
-        's                   // Lifetime parameter
-    );
+        #[test]
+        fn derive_id_enum_variants() {
+            assert_eq!(&[Role::Admin, Role::ReadOnly], Role::VARIANTS);
+            assert_eq!(&[Service::WebApp, Service::Db], Service::VARIANTS);
+        }
+    }
 
     #[test]
     fn new() {
@@ -560,6 +2241,421 @@ mod tests {
         assert_eq!("one", Borrow::<str>::borrow(&&my_id));
     }
 
+    #[test]
+    fn generated_macro() {
+        let my_id = my_id_static_macro!("one");
+
+        assert_eq!(MyIdType2::new_unchecked("one"), my_id);
+    }
+
+    #[test]
+    fn generated_macro_accepts_underscore_and_alpha_start() {
+        assert_eq!(MyIdType2::new_unchecked("_"), my_id_static_macro!("_"));
+        assert_eq!(MyIdType2::new_unchecked("a"), my_id_static_macro!("a"));
+        assert_eq!(MyIdType2::new_unchecked("A"), my_id_static_macro!("A"));
+    }
+
+    #[cfg(feature = "macros")]
+    #[test]
+    fn checked_id_with_type_path() {
+        let my_id = crate::checked_id!(self::MyIdType, "one");
+
+        assert_eq!(MyIdType::new_unchecked("one"), my_id);
+    }
+
+    #[test]
+    fn grammar_is_valid_id() {
+        assert!(K8sName::is_valid_id("web-server-2"));
+        assert!(!K8sName::is_valid_id("Web"));
+        assert!(!K8sName::is_valid_id("web_server"));
+        assert!(!K8sName::is_valid_id("web-"));
+    }
+
+    #[test]
+    fn grammar_try_from_and_from_str() {
+        use std::str::FromStr;
+
+        assert!(K8sName::try_from("web-server").is_ok());
+        assert!(K8sName::try_from(String::from("_web")).is_err());
+        assert!(K8sName::from_str("web-").is_err());
+    }
+
+    #[test]
+    fn grammar_error_display() {
+        let error = K8sName::new("web_server").unwrap_err();
+
+        assert_eq!(
+            "`web_server` is not a valid `K8sName`: invalid character `_` at column 4.\n    \
+            web_server\n       \
+            ^\n\
+            `K8sName`s must begin with a lowercase letter, and contain only lowercase letters, numbers, or hyphens, \
+            and satisfy `self::no_trailing_hyphen`.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn grammar_predicate_error() {
+        let error = K8sName::new("web-").unwrap_err();
+
+        assert_eq!(InvalidReason::Predicate, error.reason());
+        assert_eq!(None, error.invalid_char());
+    }
+
+    #[test]
+    fn grammar_generated_macro() {
+        let k8s_name = k8s_name!("web-server");
+
+        assert_eq!(K8sName::new_unchecked("web-server"), k8s_name);
+    }
+
+    #[test]
+    fn style_is_valid_id() {
+        assert!(ProfileId::is_valid_id("web_server_2"));
+        assert!(!ProfileId::is_valid_id("WeB__x_"));
+        assert!(!ProfileId::is_valid_id("web__x"));
+        assert!(!ProfileId::is_valid_id("web_x_"));
+        assert!(!ProfileId::is_valid_id("_web"));
+
+        assert!(EnvVarId::is_valid_id("RUST_LOG"));
+        assert!(!EnvVarId::is_valid_id("Rust_Log"));
+        assert!(!EnvVarId::is_valid_id("RUST__LOG"));
+        assert!(!EnvVarId::is_valid_id("RUST_LOG_"));
+    }
+
+    #[test]
+    fn style_error_display() {
+        let error = ProfileId::new("WeB__x_").unwrap_err();
+
+        assert_eq!(InvalidReason::UppercaseLetter, error.reason());
+        assert_eq!(
+            "`WeB__x_` is not a valid `ProfileId`: uppercase letter `W` at column 1.\n    \
+            WeB__x_\n    \
+            ^\n\
+            `ProfileId`s must begin with a lowercase letter, and contain only lowercase letters, numbers, or underscores, \
+            and be `snake_case` without repeated or trailing underscores.",
+            error.to_string()
+        );
+
+        let error = EnvVarId::new("RUST__LOG").unwrap_err();
+        assert_eq!(InvalidReason::RepeatedUnderscore, error.reason());
+        assert_eq!(5, error.offset());
+
+        let error = EnvVarId::new("RUST_LOG_").unwrap_err();
+        assert_eq!(InvalidReason::TrailingUnderscore, error.reason());
+        assert_eq!(8, error.offset());
+    }
+
+    #[test]
+    fn style_generated_macro_and_from_lossy() {
+        assert_eq!(
+            ProfileId::new_unchecked("web_server"),
+            profile_id!("web_server")
+        );
+        assert_eq!(EnvVarId::new_unchecked("RUST_LOG"), env_var_id!("RUST_LOG"));
+        assert_eq!("web_server", ProfileId::from_lossy("Web  Server!").as_str());
+        assert_eq!("RUST_LOG", EnvVarId::from_lossy("rust__log_").as_str());
+        assert_eq!("^[a-z][a-z0-9_]*$", ProfileId::PATTERN);
+    }
+
+    #[test]
+    fn error_reason_offset_and_char() {
+        let error = MyIdType::new("").unwrap_err();
+        assert_eq!(InvalidReason::Empty, error.reason());
+        assert_eq!(0, error.offset());
+        assert_eq!(None, error.invalid_char());
+
+        let error = MyIdType::new("1abc").unwrap_err();
+        assert_eq!(InvalidReason::InvalidFirstChar, error.reason());
+        assert_eq!(0, error.offset());
+        assert_eq!(Some('1'), error.invalid_char());
+
+        let error = MyIdType::new("café").unwrap_err();
+        assert_eq!(InvalidReason::InvalidChar, error.reason());
+        assert_eq!(3, error.offset());
+        assert_eq!(Some('é'), error.invalid_char());
+    }
+
+    #[test]
+    fn error_display() {
+        let error = MyIdType::new("invalid with space").unwrap_err();
+
+        assert_eq!(
+            "`invalid with space` is not a valid `MyIdType`: invalid character ` ` at column 8.\n    \
+            invalid with space\n           \
+            ^\n\
+            `MyIdType`s must begin with a letter or underscore, and contain only letters, numbers, or underscores.",
+            error.to_string()
+        );
+
+        let error = MyIdType::new("").unwrap_err();
+
+        assert_eq!(
+            "`` is not a valid `MyIdType`: the value is empty.\n\
+            `MyIdType`s must begin with a letter or underscore, and contain only letters, numbers, or underscores.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn length_limits() {
+        assert!(ShortId::is_valid_id("ab"));
+        assert!(ShortId::is_valid_id("abcdefgh"));
+
+        let error = ShortId::new("a").unwrap_err();
+        assert_eq!(InvalidReason::TooShort, error.reason());
+
+        let error = ShortId::try_from(String::from("abcdefghi")).unwrap_err();
+        assert_eq!(InvalidReason::TooLong, error.reason());
+        assert_eq!(8, error.offset());
+    }
+
+    #[test]
+    fn length_limit_from_str_truncates_error_value() {
+        use std::str::FromStr;
+
+        let long_value = "a".repeat(1024 * 1024);
+        let error = ShortId::from_str(&long_value).unwrap_err();
+
+        assert_eq!(InvalidReason::TooLong, error.reason());
+        assert_eq!("aaaaaaaa", error.value());
+        assert_eq!(
+            "`aaaaaaaa...` is not a valid `ShortId`: the value is too long.\n\
+            `ShortId`s must begin with a letter or underscore, and contain only letters, numbers, or underscores, \
+            and be 2 to 8 characters long.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn length_limits_generated_macro() {
+        let short_id = short_id!("abcdefgh");
+
+        assert_eq!(ShortId::new_unchecked("abcdefgh"), short_id);
+    }
+
+    #[test]
+    fn pattern() {
+        assert_eq!("^[A-Za-z_][A-Za-z0-9_]*$", MyIdType::PATTERN);
+        assert_eq!("^[a-z][a-z0-9\\-]*$", K8sName::PATTERN);
+        assert_eq!("^[A-Za-z_][A-Za-z0-9_]{1,7}$", ShortId::PATTERN);
+        assert_eq!("^[a-z][A-Za-z0-9_]*$", StringId::PATTERN);
+        assert_eq!(MyIdType3::RULES.pattern(), MyIdType3::PATTERN);
+        assert_eq!(TableName::RULES.pattern(), TableName::PATTERN);
+    }
+
+    #[test]
+    fn reserved_words() {
+        assert!(TableName::is_valid_id("orders"));
+        assert!(TableName::is_valid_id("Self_"));
+
+        let error = TableName::new("SELECT").unwrap_err();
+        assert_eq!(InvalidReason::Reserved, error.reason());
+
+        let error = TableName::new("fn").unwrap_err();
+        assert_eq!(InvalidReason::Reserved, error.reason());
+
+        let error = TableName::new("internal").unwrap_err();
+        assert_eq!(InvalidReason::Reserved, error.reason());
+
+        let error = TableName::new("tmp_orders").unwrap_err();
+        assert_eq!(InvalidReason::ReservedPrefix, error.reason());
+        assert_eq!(4, error.offset());
+        assert_eq!(
+            "`tmp_orders` is not a valid `TableName`: the value begins with a reserved prefix.\n\
+            `TableName`s must begin with a letter or underscore, and contain only letters, numbers, or underscores, \
+            and not be a reserved word, and not begin with `__` or `tmp_`.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn reserved_words_generated_macro() {
+        let table_name = table_name!("orders");
+
+        assert_eq!(TableName::new_unchecked("orders"), table_name);
+    }
+
+    #[test]
+    fn new_const() {
+        const WEB: MyIdType = MyIdType::new_const("web");
+        const WEB_SERVER: K8sName = K8sName::new_const("web-server");
+        const ONE: MyIdType3<'static> = MyIdType3::new_const("one");
+
+        assert_eq!(MyIdType::new_unchecked("web"), WEB);
+        assert_eq!(K8sName::new_unchecked("web-server"), WEB_SERVER);
+        assert_eq!(MyIdType3::new_unchecked("one"), ONE);
+        assert!(const { K8sName::is_valid_id("web") });
+        assert!(!const { K8sName::is_valid_id("web-") });
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "`a b` is not a valid `MyIdType`: invalid character ` ` at column 2."
+    )]
+    fn new_const_invalid_panics_at_runtime() {
+        let value = String::from("a b");
+        let value: &'static str = value.leak();
+
+        MyIdType::new_const(value);
+    }
+
+    #[test]
+    #[should_panic(expected = "`abcdefgh...` is not a valid `ShortId`: the value is too long.")]
+    fn new_const_too_long_panics_at_runtime() {
+        ShortId::new_const("abcdefghi");
+    }
+
+    #[test]
+    fn from_lossy() {
+        assert_eq!(
+            MyIdType::new_unchecked("Web_Server_2"),
+            MyIdType::from_lossy("Web Server #2")
+        );
+        assert_eq!(
+            K8sName::new_unchecked("web-server-2"),
+            K8sName::from_lossy("Web Server #2")
+        );
+        assert_eq!(
+            TableName::new_unchecked("select_"),
+            TableName::from_lossy("select")
+        );
+        assert_eq!(
+            MyIdType3::new_unchecked("_3rd_party"),
+            MyIdType3::from_lossy("3rd party")
+        );
+    }
+
+    #[test]
+    fn from_lossy_applies_predicate() {
+        [
+            ("web-", "web"),
+            ("Web Server -", "web-server"),
+            ("-", "a"),
+            ("a--", "a"),
+        ]
+        .into_iter()
+        .for_each(|(value, expected)| {
+            let k8s_name = K8sName::from_lossy(value);
+            assert_eq!(expected, k8s_name.as_str());
+            assert!(K8sName::is_valid_id(k8s_name.as_str()), "{k8s_name}");
+        });
+
+        [("words", "words"), ("Web Server #2", "_"), ("", "_")]
+            .into_iter()
+            .for_each(|(value, expected)| {
+                let word_id = WordId::from_lossy(value);
+                assert_eq!(expected, word_id.as_str());
+                assert!(WordId::is_valid_id(word_id.as_str()), "{word_id}");
+            });
+    }
+
+    #[test]
+    fn encode_decode() {
+        let my_id = MyIdType::encode("user@example.com").unwrap();
+
+        assert_eq!("user_40_example_2E_com", my_id.as_str());
+        assert_eq!(Ok(String::from("user@example.com")), my_id.decode());
+
+        let my_id = MyIdType3::encode("src/main.rs").unwrap();
+
+        assert_eq!("src_2F_main_2E_rs", my_id.as_str());
+        assert_eq!(Ok(String::from("src/main.rs")), my_id.decode());
+    }
+
+    #[test]
+    fn encode_invalid() {
+        let error = MyIdType::encode("").unwrap_err();
+        assert_eq!(InvalidReason::Empty, error.reason());
+
+        let error = ShortId::encode("a/b/c").unwrap_err();
+        assert_eq!(InvalidReason::TooLong, error.reason());
+
+        let error = K8sName::encode("a/b").unwrap_err();
+        assert_eq!(InvalidReason::InvalidChar, error.reason());
+    }
+
+    // Tests for `ids!` and `declare_ids!`
+    #[cfg(feature = "macros")]
+    mod checked_lists {
+        use super::{K8sName, MyIdType};
+
+        crate::declare_ids! {
+            super::K8sName;
+
+            /// The web server.
+            pub const WEB = "web-server";
+            const DB = "db";
+            const ALL_SERVICES;
+        }
+
+        crate::declare_ids! {
+            super::MyIdType;
+
+            const ONE = "one";
+            pub(super) const ALL_MY_IDS;
+        }
+
+        #[test]
+        fn declare_ids() {
+            assert_eq!(K8sName::new_unchecked("web-server"), WEB);
+            assert_eq!(K8sName::new_unchecked("db"), DB);
+            assert_eq!(&[WEB, DB], ALL_SERVICES);
+            assert_eq!(&[ONE], ALL_MY_IDS);
+        }
+
+        #[test]
+        fn ids() {
+            const MY_IDS: [MyIdType; 2] = crate::ids!(super::MyIdType; "one", "two",);
+
+            assert_eq!(
+                [
+                    MyIdType::new_unchecked("one"),
+                    MyIdType::new_unchecked("two")
+                ],
+                MY_IDS
+            );
+        }
+    }
+
+    // Tests for `id_path!`
+    #[cfg(feature = "macros")]
+    #[test]
+    fn id_path() {
+        let node = crate::id_path!(K8sName, "us-east/node-1"; separator = '/');
+
+        assert_eq!("us-east/node-1", node.to_string());
+        assert_eq!(
+            vec![
+                K8sName::new_unchecked("us-east"),
+                K8sName::new_unchecked("node-1")
+            ],
+            node.segments().cloned().collect::<Vec<_>>()
+        );
+    }
+
+    // Tests for `qualified!`
+    #[test]
+    fn qualified() {
+        crate::id_kind!(User);
+
+        enum Slash {}
+
+        impl crate::Delimiter for Slash {
+            const DELIMITER: &'static str = "/";
+        }
+
+        let item = crate::qualified!(K8sName, MyIdType, "my-plugin::item_1");
+        assert_eq!("my-plugin::item_1", item.to_string());
+
+        let user = crate::qualified!(
+            K8sName, crate::Id<'static, User>, "my-plugin/alice";
+            delimiter = Slash
+        );
+        assert_eq!("my-plugin", user.namespace().as_str());
+        assert_eq!("alice", user.local().as_str());
+    }
+
     // Tests for lifetime-parameterized ID type
     #[test]
     fn lt_new() {
@@ -614,6 +2710,372 @@ mod tests {
         drop(my_id_static);
     }
 
+    #[test]
+    fn storage_arc() {
+        let arc_id = ArcId::new("web").unwrap();
+        let arc_id_clone = arc_id.clone();
+
+        assert_eq!("web", arc_id_clone.as_str());
+        assert_eq!(ArcId::new_unchecked("web"), arc_id!("web"));
+        assert!(Arc::ptr_eq(
+            &arc_id.into_inner(),
+            &arc_id_clone.into_inner()
+        ));
+    }
+
+    #[test]
+    fn storage_try_from_and_parse() {
+        assert_eq!(
+            Box::<str>::from("web"),
+            BoxId::try_from(String::from("web")).unwrap().into_inner()
+        );
+        assert_eq!(
+            String::from("web"),
+            "web".parse::<StringId>().unwrap().into_inner()
+        );
+        assert_eq!("web", StaticStrId::try_from("web").unwrap().into_inner());
+        assert_eq!("Web_Server", BoxId::from_lossy("Web Server").to_string());
+    }
+
+    #[test]
+    fn storage_invalid_is_error() {
+        let error = StringId::new("Web").unwrap_err();
+
+        assert_eq!(InvalidReason::InvalidFirstChar, error.reason());
+        assert_eq!("Web", error.value());
+        assert!(!ArcId::is_valid_id("a b"));
+    }
+
+    #[test]
+    fn storage_borrow() {
+        let string_id = StringId::new_unchecked("web");
+        let static_str_id = StaticStrId::new_unchecked("web");
+
+        assert_eq!("web", <StringId as Borrow<str>>::borrow(&string_id));
+        assert_eq!("web", <StaticStrId as Borrow<str>>::borrow(&static_str_id));
+        assert_eq!("web", static_str_id.as_ref());
+    }
+
+    #[test]
+    fn id_newtype_trait() {
+        fn load<T: IdNewtype>(s: &str) -> Result<T, T::Error> {
+            T::try_from_str(s)
+        }
+
+        fn describe<T: IdNewtype>(id: &T) -> String {
+            format!(
+                "{} `{}` matching `{}`",
+                T::TYPE_NAME,
+                id.as_str(),
+                T::PATTERN
+            )
+        }
+
+        let k8s_name = load::<K8sName>("web-server").unwrap();
+        assert_eq!(
+            "K8sName `web-server` matching `^[a-z][a-z0-9\\-]*$`",
+            describe(&k8s_name)
+        );
+        assert_eq!(
+            InvalidReason::Predicate,
+            load::<K8sName>("web-").unwrap_err().reason()
+        );
+
+        let arc_id = load::<ArcId>("web").unwrap();
+        assert_eq!(Arc::<str>::from("web"), IdNewtype::into_inner(arc_id));
+
+        let lt_id = load::<MyIdType3<'_>>("web").unwrap();
+        assert_eq!("MyIdType3", <MyIdType3<'_> as IdNewtype>::TYPE_NAME);
+        assert_eq!(Cow::<str>::Borrowed("web"), IdNewtype::into_inner(lt_id));
+    }
+
+    #[test]
+    fn id_newtype_trait_validates_with_predicate() {
+        fn is_valid<T: IdNewtype>(s: &str) -> bool {
+            T::is_valid_id(s)
+        }
+
+        assert!(is_valid::<K8sName>("web"));
+        assert!(!is_valid::<K8sName>("web-"));
+        assert!(<K8sName as IdNewtype>::RULES.is_valid_id("web-"));
+        assert!(!is_valid::<TableName>("__web"));
+    }
+
+    #[test]
+    fn split_index() {
+        fn split_index(s: &'static str) -> (String, Option<u64>) {
+            let id = MyIdType::new(s).unwrap();
+            let (base, index) = id.split_index();
+            (base.to_string(), index)
+        }
+
+        assert_eq!(("web".to_string(), Some(3)), split_index("web_3"));
+        assert_eq!(
+            ("web_server".to_string(), Some(12)),
+            split_index("web_server_12")
+        );
+        assert_eq!(("web_03".to_string(), None), split_index("web_03"));
+        assert_eq!(("web3".to_string(), None), split_index("web3"));
+        assert_eq!(("_3".to_string(), None), split_index("_3"));
+        assert_eq!(
+            ("web", Some(2)),
+            K8sName::new("web-2").unwrap().split_index()
+        );
+    }
+
+    #[test]
+    fn uniquify() {
+        let taken = ["web", "web_2", "web_3"];
+        let web = MyIdType::new("web").unwrap();
+
+        assert_eq!("web_4", web.uniquify_in(&taken[..]).unwrap().as_str());
+        assert_eq!(
+            "web_4",
+            MyIdType::new("web_2")
+                .unwrap()
+                .uniquify_in(&taken[..])
+                .unwrap()
+                .as_str()
+        );
+        assert_eq!(
+            "db",
+            MyIdType::new("db")
+                .unwrap()
+                .uniquify_in(&taken[..])
+                .unwrap()
+                .as_str()
+        );
+
+        let taken = HashSet::from([K8sName::new("web").unwrap()]);
+        let web = K8sName::new("web").unwrap();
+        assert_eq!("web-2", web.uniquify_in(&taken).unwrap().as_str());
+    }
+
+    #[test]
+    fn uniquify_truncates_base_to_max_len() {
+        let is_taken = |candidate: &str| {
+            candidate == "abcdefgh" || (candidate.starts_with("abcdef_") && candidate.len() == 8)
+        };
+        let id = ShortId::new("abcdefgh")
+            .unwrap()
+            .uniquify(is_taken)
+            .unwrap();
+
+        assert_eq!("abcde_10", id.as_str());
+    }
+
+    #[test]
+    fn uniquify_trims_separator_from_truncated_base() {
+        let id = ShortSnakeId::new("ab_cd").unwrap();
+        let uniquified = id.uniquify(|candidate| candidate == "ab_cd").unwrap();
+
+        assert_eq!("ab_2", uniquified.as_str());
+    }
+
+    #[test]
+    fn uniquify_skips_invalid_variants() {
+        let id = TableName::new("web").unwrap();
+        let uniquified = id.uniquify(|candidate| candidate == "web").unwrap();
+
+        assert_eq!("web_3", uniquified.as_str());
+    }
+
+    #[test]
+    fn uniquify_with_invalid_variant_is_error() {
+        let id = TableName::new("tmp").unwrap();
+        let error = id.uniquify(|candidate| candidate == "tmp").unwrap_err();
+
+        let UniquifyError::InvalidId(error) = error else {
+            panic!("Expected the free variant to be invalid, but got `{error:?}`.");
+        };
+        assert_eq!("tmp_2", error.value());
+        assert_eq!(InvalidReason::ReservedPrefix, error.reason());
+    }
+
+    #[test]
+    fn uniquify_when_all_taken_is_error() {
+        let id = MyIdType::new("web").unwrap();
+        let error = id.uniquify(|_| true).unwrap_err();
+
+        assert_eq!(
+            UniquifyError::Exhausted {
+                value: String::from("web"),
+                attempts: 10_000,
+            },
+            error
+        );
+        assert_eq!(
+            "No free variant of `web` was found after 10000 attempts.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn uniquify_with_max_index_is_error() {
+        let id = MyIdType::new("web_18446744073709551614").unwrap();
+        let error = id.uniquify(|_| true).unwrap_err();
+
+        assert_eq!(
+            UniquifyError::Exhausted {
+                value: String::from("web_18446744073709551614"),
+                attempts: 1,
+            },
+            error
+        );
+
+        let id = MyIdType::new("web_18446744073709551615").unwrap();
+        let error = id.uniquify(|_| true).unwrap_err();
+
+        assert_eq!(
+            UniquifyError::Exhausted {
+                value: String::from("web_18446744073709551615"),
+                attempts: 0,
+            },
+            error
+        );
+    }
+
+    #[test]
+    fn case_conversions() {
+        let id = MyIdType::new("web_HTTPServer").unwrap();
+
+        assert_eq!(
+            vec!["web", "HTTP", "Server"],
+            id.words().collect::<Vec<_>>()
+        );
+        assert_eq!("webHttpServer", id.to_camel_case().as_str());
+        assert_eq!("WebHttpServer", id.to_pascal_case().as_str());
+        assert_eq!("WEB_HTTP_SERVER", id.to_screaming_snake_case().as_str());
+        assert_eq!(
+            "web_http_server",
+            id.to_pascal_case().to_snake_case().as_str()
+        );
+        assert_eq!(
+            "web_server",
+            MyIdType::from_camel_case("webServer").unwrap().as_str()
+        );
+        assert_eq!(
+            "web_server",
+            MyIdType::from_screaming_snake_case("WEB_SERVER")
+                .unwrap()
+                .as_str()
+        );
+    }
+
+    #[test]
+    fn display_case() {
+        let id = K8sName::new("web-server-2").unwrap();
+
+        assert_eq!("WebServer2", id.display_case(CaseStyle::Pascal).to_string());
+        assert_eq!(
+            "web_server2",
+            K8sName::from_pascal_case("WebServer2").unwrap_err().value()
+        );
+    }
+
+    #[test]
+    fn edit_commits_valid_values() {
+        let mut id = MyIdType::new("web").unwrap();
+        let mut edit = id.edit();
+        edit.push_str("_server");
+        edit.commit().unwrap();
+        assert_eq!("web_server", id.as_str());
+
+        let mut edit = id.edit();
+        edit.insert(0, ' ');
+        let error = edit.commit().unwrap_err();
+        assert_eq!(" web_server", error.value());
+        assert_eq!("web_server", id.as_str());
+
+        let mut edit = id.edit();
+        edit.clear();
+        drop(edit);
+        assert_eq!("web_server", id.as_str());
+    }
+
+    #[test]
+    fn mutators_static() {
+        let mut id = K8sName::new("web").unwrap();
+
+        id.push_segment(&MyIdType::new("server").unwrap()).unwrap();
+        assert_eq!("web-server", id.as_str());
+
+        id.replace_range(4.., "db").unwrap();
+        assert_eq!("web-db", id.as_str());
+
+        let error = id.replace_range(3..4, "_").unwrap_err();
+        assert_eq!(InvalidReason::InvalidChar, error.reason());
+        assert_eq!("web-db", id.as_str());
+
+        assert_eq!("my-web-db", id.with_prefix("my-").unwrap().as_str());
+        assert_eq!("web-db-2", id.with_suffix("-2").unwrap().as_str());
+        assert!(id.with_suffix("-").is_err());
+    }
+
+    #[test]
+    fn mutators_lifetime() {
+        let mut id = MyIdType3::new("web").unwrap();
+
+        id.push_segment("server").unwrap();
+        assert_eq!("web_server", id.as_str());
+
+        id.replace_range(..3, "app").unwrap();
+        assert_eq!("app_server", id.as_str());
+
+        assert!(id.push_segment("a b").is_err());
+        assert_eq!("app_server", id.as_str());
+
+        assert_eq!("my_app_server", id.with_prefix("my_").unwrap().as_str());
+        assert_eq!("app_server_2", id.with_suffix("_2").unwrap().as_str());
+        assert!(id.with_prefix("1").is_err());
+    }
+
+    #[test]
+    fn push_segment_checks_max_len() {
+        let mut id = ShortId::new("abcd").unwrap();
+
+        let error = id.push_segment("efgh").unwrap_err();
+        assert_eq!(InvalidReason::TooLong, error.reason());
+        assert_eq!("abcd", id.as_str());
+    }
+
+    #[test]
+    fn interned() {
+        let web = MyIdType::new_unchecked("web").intern();
+        let db = Interned::new(&MyIdType::new_unchecked("db"));
+
+        assert_eq!(web, Interned::new(&MyIdType::new_unchecked("web")));
+        assert_ne!(web, db);
+        assert_eq!("web", web.resolve());
+        assert_eq!(MyIdType::new_unchecked("db"), db.to_id());
+        assert_eq!(r#"Interned("web")"#, format!("{web:?}"));
+    }
+
+    #[test]
+    fn interned_types_have_separate_interners() {
+        let web = MyIdType2::new_unchecked("interned_web").intern();
+
+        assert_eq!(
+            Some(web),
+            Interned::get(&MyIdType2::new_unchecked("interned_web"))
+        );
+        assert_eq!(
+            None,
+            Interned::get(&MyIdType::new_unchecked("interned_web"))
+        );
+    }
+
+    #[test]
+    fn lt_interned() {
+        let s = String::from("one");
+        let interned = MyIdType3::new_unchecked(s.as_str()).intern();
+
+        drop(s);
+
+        assert_eq!("one", interned.resolve());
+        assert_eq!(MyIdType3::new_unchecked("one"), interned.to_id());
+    }
+
     #[test]
     fn lt_as_str() {
         let my_id = MyIdType3::new_unchecked("one");
@@ -636,6 +3098,13 @@ mod tests {
         assert_eq!("one", Borrow::<str>::borrow(&&my_id));
     }
 
+    #[test]
+    fn lt_generated_macro() {
+        let my_id = my_id_lt_macro!("one");
+
+        assert_eq!(MyIdType3::new_unchecked("one"), my_id);
+    }
+
     #[test]
     fn lt_from_str() {
         use std::str::FromStr;
@@ -657,6 +3126,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::explicit_auto_deref)] // Checks `Deref` explicitly.
     fn lt_deref() {
         let my_id = MyIdType3::new_unchecked("one");
         let cow: &Cow<'_, str> = &*my_id;
@@ -0,0 +1,161 @@
+/// Generated macros reject invalid IDs at compile time, checked with the
+/// type's `validate`.
+///
+/// Names may begin with an underscore or a letter:
+///
+/// ```rust
+/// # use std::borrow::Cow;
+/// #
+/// # #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// # pub struct MyId(Cow<'static, str>);
+/// # id_newtype::id_newtype!(MyId, MyIdInvalidFmt, my_id);
+/// #
+/// # fn main() {
+/// let _ = [my_id!("_"), my_id!("a"), my_id!("A")];
+/// # }
+/// ```
+///
+/// Names may not begin with a number:
+///
+/// ```rust,compile_fail,E0080
+/// # use std::borrow::Cow;
+/// #
+/// # #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// # pub struct MyId(Cow<'static, str>);
+/// # id_newtype::id_newtype!(MyId, MyIdInvalidFmt, my_id);
+/// #
+/// # fn main() {
+/// let _ = my_id!("1");
+/// # }
+/// ```
+///
+/// Names may not contain a space:
+///
+/// ```rust,compile_fail,E0080
+/// # use std::borrow::Cow;
+/// #
+/// # #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// # pub struct MyId(Cow<'static, str>);
+/// # id_newtype::id_newtype!(MyId, MyIdInvalidFmt, my_id);
+/// #
+/// # fn main() {
+/// let _ = my_id!("a b");
+/// # }
+/// ```
+///
+/// Names may not contain a hyphen:
+///
+/// ```rust,compile_fail,E0080
+/// # use std::borrow::Cow;
+/// #
+/// # #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// # pub struct MyId(Cow<'static, str>);
+/// # id_newtype::id_newtype!(MyId, MyIdInvalidFmt, my_id);
+/// #
+/// # fn main() {
+/// let _ = my_id!("a-b");
+/// # }
+/// ```
+///
+/// Names may not be empty:
+///
+/// ```rust,compile_fail,E0080
+/// # use std::borrow::Cow;
+/// #
+/// # #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// # pub struct MyId(Cow<'static, str>);
+/// # id_newtype::id_newtype!(MyId, MyIdInvalidFmt, my_id);
+/// #
+/// # fn main() {
+/// let _ = my_id!("");
+/// # }
+/// ```
+///
+/// A macro call without a name is checked as the empty name:
+///
+/// ```rust,compile_fail,E0080
+/// # use std::borrow::Cow;
+/// #
+/// # #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// # pub struct MyId(Cow<'static, str>);
+/// # id_newtype::id_newtype!(MyId, MyIdInvalidFmt, my_id);
+/// #
+/// # fn main() {
+/// let _ = my_id!();
+/// # }
+/// ```
+pub struct MacroCompileFail;
+
+/// The `"macros"` feature's macros reject invalid IDs at compile time, checked
+/// with the type's `validate`.
+///
+/// Valid names compile:
+///
+/// ```rust
+/// # use std::borrow::Cow;
+/// #
+/// # mod ids {
+/// #     use std::borrow::Cow;
+/// #
+/// #     #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// #     pub struct MyId(Cow<'static, str>);
+/// #     id_newtype::id_newtype!(MyId, MyIdInvalidFmt);
+/// # }
+/// #
+/// # #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// # pub struct Id(Cow<'static, str>);
+/// # id_newtype::id_newtype!(Id, IdInvalidFmt);
+/// #
+/// use ids::MyId;
+///
+/// let _ = id_newtype::checked_id!(ids::MyId, "_web");
+/// let _ = id_newtype::id!("Web");
+/// let _ = id_newtype::ids!(MyId; "web", "db");
+/// let _ = id_newtype::id_path!(MyId, "us_east.node_1");
+/// ```
+///
+/// `checked_id!` accepts a type path:
+///
+/// ```rust,compile_fail,E0080
+/// # mod ids {
+/// #     use std::borrow::Cow;
+/// #
+/// #     #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// #     pub struct MyId(Cow<'static, str>);
+/// #     id_newtype::id_newtype!(MyId, MyIdInvalidFmt);
+/// # }
+/// #
+/// let _ = id_newtype::checked_id!(ids::MyId, "1");
+/// ```
+///
+/// ```rust,compile_fail,E0080
+/// # use std::borrow::Cow;
+/// #
+/// # #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// # pub struct Id(Cow<'static, str>);
+/// # id_newtype::id_newtype!(Id, IdInvalidFmt);
+/// #
+/// let _ = id_newtype::id!("a-b");
+/// ```
+///
+/// ```rust,compile_fail,E0080
+/// # use std::borrow::Cow;
+/// #
+/// # #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// # pub struct MyId(Cow<'static, str>);
+/// # id_newtype::id_newtype!(MyId, MyIdInvalidFmt);
+/// #
+/// let _ = id_newtype::ids!(MyId; "web", "a b");
+/// ```
+///
+/// ```rust,compile_fail,E0080
+/// # use std::borrow::Cow;
+/// #
+/// # #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// # pub struct MyId(Cow<'static, str>);
+/// # id_newtype::id_newtype!(MyId, MyIdInvalidFmt);
+/// #
+/// let _ = id_newtype::id_path!(MyId, "us_east..node_1");
+/// ```
+#[cfg(feature = "macros")]
+pub struct ProcMacroCompileFail;
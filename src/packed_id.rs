@@ -0,0 +1,319 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+};
+
+use crate::IdPackError;
+
+/// `Copy` ID of up to 21 characters, packed into a `u128`.
+///
+/// Each character in `[A-Za-z0-9_]` is stored in 6 bits, so packed IDs compare
+/// and hash as an integer. Packed IDs are ordered the same way as their
+/// strings.
+///
+/// IDs that are longer than [`PackedId::MAX_LEN`], or that contain other
+/// characters allowed by a custom grammar, cannot be packed.
+///
+/// ```rust
+/// use std::borrow::Cow;
+///
+/// use id_newtype::PackedId;
+///
+/// #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// pub struct MyId(Cow<'static, str>);
+///
+/// id_newtype::id_newtype!(MyId, MyIdInvalidFmt);
+///
+/// let web = MyId::new("web").unwrap();
+/// let packed_web = PackedId::new(&web).unwrap();
+///
+/// assert_eq!(Ok(web), packed_web.to_id());
+/// assert!(PackedId::new(&MyId::new("a_very_long_id_of_22_ch").unwrap()).is_err());
+/// ```
+pub struct PackedId<T> {
+    /// Characters of the ID, with the first character in the highest bits, and
+    /// `0` after the last character.
+    bits: u128,
+    /// Marker for the ID type.
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> PackedId<T> {
+    /// Number of bits for each character.
+    const CHAR_BITS: u32 = 6;
+    /// Mask for the bits of one character.
+    const CHAR_MASK: u128 = (1 << Self::CHAR_BITS) - 1;
+    /// Maximum number of characters in a packed ID.
+    pub const MAX_LEN: usize = 21;
+
+    /// Returns the packed bits of the ID.
+    pub const fn to_bits(self) -> u128 {
+        self.bits
+    }
+
+    /// Returns the number of characters in the ID.
+    pub const fn len(self) -> usize {
+        let mut len = 0;
+        while len < Self::MAX_LEN && Self::symbol_at(self.bits, len) != 0 {
+            len += 1;
+        }
+        len
+    }
+
+    /// Returns whether the ID is empty.
+    pub const fn is_empty(self) -> bool {
+        self.bits == 0
+    }
+
+    /// Returns the packed bits of the given string.
+    const fn pack(s: &str) -> Result<u128, IdPackError> {
+        let bytes = s.as_bytes();
+        if bytes.len() > Self::MAX_LEN {
+            return Err(IdPackError::TooLong {
+                len: crate::const_str::char_count(s, bytes.len()),
+            });
+        }
+
+        let mut bits = 0u128;
+        let mut index = 0;
+        while index < Self::MAX_LEN {
+            let symbol = if index < bytes.len() {
+                match Self::symbol(bytes[index]) {
+                    Some(symbol) => symbol,
+                    None => {
+                        return Err(IdPackError::InvalidChar {
+                            invalid_char: crate::const_str::char_at(s, index),
+                            offset: index,
+                        });
+                    }
+                }
+            } else {
+                0
+            };
+            bits = (bits << Self::CHAR_BITS) | symbol as u128;
+            index += 1;
+        }
+
+        Ok(bits)
+    }
+
+    /// Returns the string of the packed bits.
+    fn unpack(bits: u128) -> String {
+        (0..Self::MAX_LEN)
+            .map(|index| Self::symbol_at(bits, index))
+            .take_while(|symbol| *symbol != 0)
+            .map(|symbol| char::from(Self::symbol_char(symbol)))
+            .collect()
+    }
+
+    /// Returns the symbol of the character at the given index.
+    const fn symbol_at(bits: u128, index: usize) -> u8 {
+        let shift = (Self::MAX_LEN - 1 - index) as u32 * Self::CHAR_BITS;
+        ((bits >> shift) & Self::CHAR_MASK) as u8
+    }
+
+    /// Returns the symbol for a character, in the same order as ASCII so that
+    /// packed IDs are ordered like their strings.
+    ///
+    /// `0` is reserved for the end of the ID.
+    const fn symbol(c: u8) -> Option<u8> {
+        match c {
+            b'0'..=b'9' => Some(c - b'0' + 1),
+            b'A'..=b'Z' => Some(c - b'A' + 11),
+            b'_' => Some(37),
+            b'a'..=b'z' => Some(c - b'a' + 38),
+            _ => None,
+        }
+    }
+
+    /// Returns the character for a non-zero symbol.
+    const fn symbol_char(symbol: u8) -> u8 {
+        match symbol {
+            1..=10 => symbol - 1 + b'0',
+            11..=36 => symbol - 11 + b'A',
+            37 => b'_',
+            _ => symbol - 38 + b'a',
+        }
+    }
+}
+
+impl<T> PackedId<T>
+where
+    T: AsRef<str> + TryFrom<String>,
+{
+    /// Returns the packed form of the given ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ID is longer than [`PackedId::MAX_LEN`], or
+    /// contains characters outside `[A-Za-z0-9_]`.
+    pub fn new(id: &T) -> Result<Self, IdPackError> {
+        Self::pack(id.as_ref()).map(|bits| Self {
+            bits,
+            marker: PhantomData,
+        })
+    }
+
+    /// Returns the ID that was packed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the unpacked string is not a valid `T`, e.g. if the
+    /// packed ID was created with `new_unchecked`.
+    pub fn to_id(self) -> Result<T, <T as TryFrom<String>>::Error> {
+        T::try_from(Self::unpack(self.bits))
+    }
+}
+
+// These are implemented manually so that `T` does not need to implement them.
+
+impl<T> Clone for PackedId<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for PackedId<T> {}
+
+impl<T> PartialEq for PackedId<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bits == other.bits
+    }
+}
+
+impl<T> Eq for PackedId<T> {}
+
+impl<T> PartialOrd for PackedId<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for PackedId<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bits.cmp(&other.bits)
+    }
+}
+
+impl<T> Hash for PackedId<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bits.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for PackedId<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PackedId")
+            .field(&Self::unpack(self.bits))
+            .finish()
+    }
+}
+
+impl<T> fmt::Display for PackedId<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&Self::unpack(self.bits))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::PackedId;
+    use crate::IdPackError;
+
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    pub struct K8sName(Cow<'static, str>);
+
+    crate::id_newtype!(K8sName, K8sNameInvalidFmt; first = "a-z", rest = "a-z0-9-");
+
+    fn packed(s: &'static str) -> PackedId<K8sName> {
+        PackedId::new(&K8sName::new_unchecked(s)).unwrap()
+    }
+
+    #[test]
+    fn pack_round_trips() {
+        ["a", "web", "abcdefghijklmnopqrstu", "z9"]
+            .into_iter()
+            .for_each(|s| {
+                let packed = packed(s);
+
+                assert_eq!(Ok(K8sName::new_unchecked(s)), packed.to_id());
+                assert_eq!(s.len(), packed.len());
+                assert_eq!(s, packed.to_string());
+            });
+    }
+
+    #[test]
+    fn unpack_invalid_id_is_error() {
+        let packed = packed("Web");
+
+        assert_eq!(
+            "`Web` is not a valid `K8sName`: invalid first character `W` at column 1.",
+            packed
+                .to_id()
+                .unwrap_err()
+                .to_string()
+                .lines()
+                .next()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn pack_all_symbols_round_trip() {
+        (1..=63).for_each(|symbol| {
+            let c = PackedId::<()>::symbol_char(symbol);
+            assert_eq!(Some(symbol), PackedId::<()>::symbol(c));
+        });
+        assert_eq!(
+            Ok(String::from("AZaz09_")),
+            PackedId::<()>::pack("AZaz09_").map(PackedId::<()>::unpack)
+        );
+    }
+
+    #[test]
+    fn pack_too_long_is_error() {
+        let error = PackedId::new(&K8sName::new_unchecked("abcdefghijklmnopqrstuv")).unwrap_err();
+
+        assert_eq!(IdPackError::TooLong { len: 22 }, error);
+        assert_eq!(
+            "The ID is 22 characters long, but at most 21 characters can be packed.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn pack_invalid_char_is_error() {
+        let error = PackedId::new(&K8sName::new_unchecked("web-server")).unwrap_err();
+
+        assert_eq!(
+            IdPackError::InvalidChar {
+                invalid_char: '-',
+                offset: 3
+            },
+            error
+        );
+    }
+
+    #[test]
+    fn packed_order_matches_str_order() {
+        let mut strs = ["b", "a", "ab", "a9", "a_", "aZ", "ba", "zz", "z"];
+        let mut packed_ids = strs.map(packed);
+
+        strs.sort();
+        packed_ids.sort();
+
+        assert_eq!(
+            strs.map(String::from),
+            packed_ids.map(|packed| packed.to_string())
+        );
+    }
+
+    #[test]
+    fn debug() {
+        assert_eq!(r#"PackedId("web")"#, format!("{:?}", packed("web")));
+    }
+}
@@ -0,0 +1,388 @@
+use std::{
+    borrow::Borrow,
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    str::FromStr,
+};
+
+use crate::{const_str, Delimiter, DoubleColon, IdNewtype, QualifiedError, QualifiedKey};
+
+/// ID qualified by a namespace, e.g. `my_plugin::item`.
+///
+/// The namespace and local parts may be different ID types. The delimiter
+/// defaults to `::`, and may be changed with the third type parameter, see
+/// [`Delimiter`].
+///
+/// IDs are ordered by namespace, then local part.
+///
+/// ```rust
+/// use std::borrow::Cow;
+///
+/// use id_newtype::Qualified;
+///
+/// #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// pub struct PluginId(Cow<'static, str>);
+/// id_newtype::id_newtype!(PluginId, PluginIdInvalidFmt; first = "a-z", rest = "a-z0-9_");
+///
+/// #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// pub struct ItemId(Cow<'static, str>);
+/// id_newtype::id_newtype!(ItemId, ItemIdInvalidFmt);
+///
+/// let item: Qualified<PluginId, ItemId> = "my_plugin::Item".parse().unwrap();
+///
+/// assert_eq!("my_plugin", item.namespace().as_str());
+/// assert_eq!("Item", item.local().as_str());
+/// assert_eq!("my_plugin::Item", item.to_string());
+///
+/// assert!("my_plugin".parse::<Qualified<PluginId, ItemId>>().is_err());
+/// assert!("My_Plugin::Item".parse::<Qualified<PluginId, ItemId>>().is_err());
+/// ```
+pub struct Qualified<Ns, Local, D = DoubleColon> {
+    /// Namespace part of the ID.
+    namespace: Ns,
+    /// Local part of the ID.
+    local: Local,
+    /// Marker for the delimiter.
+    marker: PhantomData<fn() -> D>,
+}
+
+impl<Ns, Local, D> Qualified<Ns, Local, D> {
+    /// Returns the namespace part of this ID.
+    pub fn namespace(&self) -> &Ns {
+        &self.namespace
+    }
+
+    /// Returns the local part of this ID.
+    pub fn local(&self) -> &Local {
+        &self.local
+    }
+
+    /// Returns the namespace and local parts of this ID.
+    pub fn into_parts(self) -> (Ns, Local) {
+        (self.namespace, self.local)
+    }
+}
+
+impl<Ns, Local, D> Qualified<Ns, Local, D>
+where
+    D: Delimiter,
+{
+    /// Returns the namespace and local parts of the given `&str`, split at
+    /// the first delimiter, if any.
+    ///
+    /// This is used by the `qualified!` macro to split the value in a `const`.
+    #[doc(hidden)]
+    pub const fn split(s: &str) -> Option<(&str, &str)> {
+        match const_str::find(s, D::DELIMITER) {
+            Some(offset) => {
+                let (namespace, rest) = s.split_at(offset);
+                Some((namespace, rest.split_at(D::DELIMITER.len()).1))
+            }
+            None => None,
+        }
+    }
+}
+
+impl<Ns, Local, D> Qualified<Ns, Local, D>
+where
+    Ns: IdNewtype,
+    Local: IdNewtype,
+    D: Delimiter,
+{
+    /// Fails to compile if the namespace type allows the first character of
+    /// the delimiter, as values could then not be split unambiguously.
+    const DELIMITER_IS_NOT_ALLOWED: () = {
+        assert!(
+            !D::DELIMITER.is_empty(),
+            "The `Qualified` delimiter must not be empty."
+        );
+        let first_char = const_str::char_at(D::DELIMITER, 0);
+        assert!(
+            !Ns::RULES.first_class().contains(first_char)
+                && !Ns::RULES.rest_class().contains(first_char),
+            "The `Qualified` delimiter must not begin with a character allowed in the namespace type."
+        );
+    };
+
+    /// Returns a new `Qualified` ID if both parts of the given `&str` are
+    /// valid.
+    pub fn new(s: &str) -> Result<Self, QualifiedError<Ns::Error, Local::Error>> {
+        let () = Self::DELIMITER_IS_NOT_ALLOWED;
+
+        let (namespace, local) =
+            Self::split(s).ok_or_else(|| QualifiedError::MissingDelimiter {
+                value: String::from(s),
+                delimiter: D::DELIMITER,
+            })?;
+        let namespace = Ns::try_from_str(namespace).map_err(QualifiedError::InvalidNamespace)?;
+        let local = Local::try_from_str(local).map_err(QualifiedError::InvalidLocal)?;
+
+        Ok(Self::from_parts(namespace, local))
+    }
+
+    /// Returns a new `Qualified` ID from its namespace and local parts.
+    pub fn from_parts(namespace: Ns, local: Local) -> Self {
+        let () = Self::DELIMITER_IS_NOT_ALLOWED;
+
+        Self {
+            namespace,
+            local,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<Ns, Local, D> FromStr for Qualified<Ns, Local, D>
+where
+    Ns: IdNewtype,
+    Local: IdNewtype,
+    D: Delimiter,
+{
+    type Err = QualifiedError<Ns::Error, Local::Error>;
+
+    fn from_str(s: &str) -> Result<Self, QualifiedError<Ns::Error, Local::Error>> {
+        Self::new(s)
+    }
+}
+
+impl<Ns, Local, D> QualifiedKey for Qualified<Ns, Local, D>
+where
+    Ns: AsRef<str>,
+    Local: AsRef<str>,
+{
+    fn namespace_str(&self) -> &str {
+        self.namespace.as_ref()
+    }
+
+    fn local_str(&self) -> &str {
+        self.local.as_ref()
+    }
+}
+
+impl<'key, Ns, Local, D> Borrow<dyn QualifiedKey + 'key> for Qualified<Ns, Local, D>
+where
+    Ns: AsRef<str> + 'key,
+    Local: AsRef<str> + 'key,
+    D: 'key,
+{
+    fn borrow(&self) -> &(dyn QualifiedKey + 'key) {
+        self
+    }
+}
+
+// These are implemented manually so that `D` does not need to implement them,
+// and so that they are consistent with `dyn QualifiedKey` for `Borrow`.
+
+impl<Ns, Local, D> Clone for Qualified<Ns, Local, D>
+where
+    Ns: Clone,
+    Local: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            namespace: self.namespace.clone(),
+            local: self.local.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<Ns, Local, D> PartialEq for Qualified<Ns, Local, D>
+where
+    Ns: AsRef<str>,
+    Local: AsRef<str>,
+{
+    fn eq(&self, other: &Self) -> bool {
+        (self as &dyn QualifiedKey) == (other as &dyn QualifiedKey)
+    }
+}
+
+impl<Ns, Local, D> Eq for Qualified<Ns, Local, D>
+where
+    Ns: AsRef<str>,
+    Local: AsRef<str>,
+{
+}
+
+impl<Ns, Local, D> PartialOrd for Qualified<Ns, Local, D>
+where
+    Ns: AsRef<str>,
+    Local: AsRef<str>,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<Ns, Local, D> Ord for Qualified<Ns, Local, D>
+where
+    Ns: AsRef<str>,
+    Local: AsRef<str>,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self as &dyn QualifiedKey).cmp(other as &dyn QualifiedKey)
+    }
+}
+
+impl<Ns, Local, D> Hash for Qualified<Ns, Local, D>
+where
+    Ns: AsRef<str>,
+    Local: AsRef<str>,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self as &dyn QualifiedKey).hash(state);
+    }
+}
+
+impl<Ns, Local, D> fmt::Display for Qualified<Ns, Local, D>
+where
+    Ns: AsRef<str>,
+    Local: AsRef<str>,
+    D: Delimiter,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}{}",
+            self.namespace.as_ref(),
+            D::DELIMITER,
+            self.local.as_ref()
+        )
+    }
+}
+
+impl<Ns, Local, D> fmt::Debug for Qualified<Ns, Local, D>
+where
+    Ns: AsRef<str>,
+    Local: AsRef<str>,
+    D: Delimiter,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Qualified").field(&self.to_string()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        borrow::Cow,
+        collections::{BTreeSet, HashMap},
+    };
+
+    use super::Qualified;
+    use crate::{Delimiter, InvalidReason, QualifiedError, QualifiedKey};
+
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    pub struct PluginId(Cow<'static, str>);
+
+    crate::id_newtype!(PluginId, PluginIdInvalidFmt; first = "a-z", rest = "a-z0-9-");
+
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    pub struct ItemId(Cow<'static, str>);
+
+    crate::id_newtype!(ItemId, ItemIdInvalidFmt);
+
+    pub enum Slash {}
+
+    impl Delimiter for Slash {
+        const DELIMITER: &'static str = "/";
+    }
+
+    type QualifiedItem = Qualified<PluginId, ItemId>;
+
+    fn item(s: &str) -> QualifiedItem {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let item = item("my-plugin::item_1");
+
+        assert_eq!("my-plugin", item.namespace().as_str());
+        assert_eq!("item_1", item.local().as_str());
+        assert_eq!("my-plugin::item_1", item.to_string());
+        assert_eq!(r#"Qualified("my-plugin::item_1")"#, format!("{item:?}"));
+        assert_eq!(
+            (
+                PluginId::new("my-plugin").unwrap(),
+                ItemId::new("item_1").unwrap()
+            ),
+            item.into_parts()
+        );
+    }
+
+    #[test]
+    fn parse_with_delimiter() {
+        let item = "my-plugin/item_1"
+            .parse::<Qualified<PluginId, ItemId, Slash>>()
+            .unwrap();
+
+        assert_eq!("my-plugin/item_1", item.to_string());
+        assert!("my-plugin::item_1"
+            .parse::<Qualified<PluginId, ItemId, Slash>>()
+            .is_err());
+    }
+
+    #[test]
+    fn parse_invalid_parts() {
+        assert_eq!(
+            Err(QualifiedError::MissingDelimiter {
+                value: String::from("my-plugin"),
+                delimiter: "::",
+            }),
+            "my-plugin".parse::<QualifiedItem>()
+        );
+
+        let Err(QualifiedError::InvalidNamespace(error)) =
+            "My-Plugin::item".parse::<QualifiedItem>()
+        else {
+            panic!("Expected the namespace to be invalid.");
+        };
+        assert_eq!(InvalidReason::InvalidFirstChar, error.reason());
+
+        let Err(QualifiedError::InvalidLocal(error)) = "my-plugin::a::b".parse::<QualifiedItem>()
+        else {
+            panic!("Expected the local ID to be invalid.");
+        };
+        assert_eq!(InvalidReason::InvalidChar, error.reason());
+        assert_eq!(
+            "`my-plugin` is not a qualified ID, as it does not contain `::`.",
+            "my-plugin"
+                .parse::<QualifiedItem>()
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn ordered_by_namespace_then_local() {
+        let items = ["b::a", "a-b::a", "a::z", "a::b"]
+            .map(item)
+            .into_iter()
+            .collect::<BTreeSet<_>>();
+
+        assert_eq!(
+            vec!["a::b", "a::z", "a-b::a", "b::a"],
+            items.iter().map(ToString::to_string).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn borrow_lookup_with_pair() {
+        let mut counts = HashMap::<QualifiedItem, u32>::new();
+        counts.insert(item("my-plugin::item_1"), 1);
+        counts.insert(item("my-plugin::item_2"), 2);
+
+        let key = ("my-plugin", "item_2");
+        assert_eq!(Some(&2), counts.get(&key as &dyn QualifiedKey));
+
+        let key = ("my-plugin", "item_3");
+        assert_eq!(None, counts.get(&key as &dyn QualifiedKey));
+
+        let items = counts.into_keys().collect::<BTreeSet<_>>();
+        let key = ("my-plugin", "item_1");
+        assert!(items.contains(&key as &dyn QualifiedKey));
+    }
+}
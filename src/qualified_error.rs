@@ -0,0 +1,51 @@
+use std::{error::Error, fmt};
+
+/// Error parsing a [`Qualified`] ID, where `NsE` and `LocalE` are the errors
+/// of the namespace and local types.
+///
+/// [`Qualified`]: crate::Qualified
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QualifiedError<NsE, LocalE> {
+    /// The value does not contain the delimiter.
+    MissingDelimiter {
+        /// The value that was provided.
+        value: String,
+        /// The delimiter that was expected.
+        delimiter: &'static str,
+    },
+    /// The namespace part is not valid.
+    InvalidNamespace(NsE),
+    /// The local part is not valid.
+    InvalidLocal(LocalE),
+}
+
+impl<NsE, LocalE> fmt::Display for QualifiedError<NsE, LocalE>
+where
+    NsE: fmt::Display,
+    LocalE: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingDelimiter { value, delimiter } => write!(
+                f,
+                "`{value}` is not a qualified ID, as it does not contain `{delimiter}`."
+            ),
+            Self::InvalidNamespace(error) => write!(f, "Invalid namespace: {error}"),
+            Self::InvalidLocal(error) => write!(f, "Invalid local ID: {error}"),
+        }
+    }
+}
+
+impl<NsE, LocalE> Error for QualifiedError<NsE, LocalE>
+where
+    NsE: Error + 'static,
+    LocalE: Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::MissingDelimiter { .. } => None,
+            Self::InvalidNamespace(error) => Some(error),
+            Self::InvalidLocal(error) => Some(error),
+        }
+    }
+}
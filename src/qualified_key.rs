@@ -0,0 +1,82 @@
+use std::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
+
+/// Namespace and local parts of a qualified ID, used to look up [`Qualified`]
+/// keys without constructing one.
+///
+/// `Qualified` implements `Borrow<dyn QualifiedKey>`, and `(&str, &str)`
+/// pairs implement this trait, so maps keyed by `Qualified` may be queried
+/// with a pair:
+///
+/// ```rust
+/// use std::{borrow::Cow, collections::HashMap};
+///
+/// use id_newtype::{Qualified, QualifiedKey};
+///
+/// #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// pub struct MyId(Cow<'static, str>);
+///
+/// id_newtype::id_newtype!(MyId, MyIdInvalidFmt);
+///
+/// let mut counts = HashMap::<Qualified<MyId, MyId>, u32>::new();
+/// counts.insert("my_plugin::item".parse().unwrap(), 3);
+///
+/// let key = ("my_plugin", "item");
+/// assert_eq!(Some(&3), counts.get(&key as &dyn QualifiedKey));
+/// ```
+///
+/// Keys are compared, ordered, and hashed by namespace, then local part.
+///
+/// [`Qualified`]: crate::Qualified
+pub trait QualifiedKey {
+    /// Returns the namespace part.
+    fn namespace_str(&self) -> &str;
+
+    /// Returns the local part.
+    fn local_str(&self) -> &str;
+}
+
+impl<Ns, Local> QualifiedKey for (Ns, Local)
+where
+    Ns: AsRef<str>,
+    Local: AsRef<str>,
+{
+    fn namespace_str(&self) -> &str {
+        self.0.as_ref()
+    }
+
+    fn local_str(&self) -> &str {
+        self.1.as_ref()
+    }
+}
+
+impl PartialEq for dyn QualifiedKey + '_ {
+    fn eq(&self, other: &Self) -> bool {
+        self.namespace_str() == other.namespace_str() && self.local_str() == other.local_str()
+    }
+}
+
+impl Eq for dyn QualifiedKey + '_ {}
+
+impl PartialOrd for dyn QualifiedKey + '_ {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for dyn QualifiedKey + '_ {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.namespace_str()
+            .cmp(other.namespace_str())
+            .then_with(|| self.local_str().cmp(other.local_str()))
+    }
+}
+
+impl Hash for dyn QualifiedKey + '_ {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.namespace_str().hash(state);
+        self.local_str().hash(state);
+    }
+}
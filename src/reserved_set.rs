@@ -0,0 +1,177 @@
+use std::fmt;
+
+use crate::const_str;
+
+/// Built-in set of reserved words that an ID type may opt into.
+///
+/// ```rust
+/// use std::borrow::Cow;
+///
+/// #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// pub struct TableName(Cow<'static, str>);
+///
+/// id_newtype::id_newtype!(
+///     TableName,
+///     TableNameInvalidFmt;
+///     reserved_sets = [Rust, Sql],
+/// );
+///
+/// assert!(!TableName::is_valid_id("fn"));
+/// assert!(!TableName::is_valid_id("SELECT"));
+/// assert!(TableName::is_valid_id("orders"));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ReservedSet {
+    /// Rust strict and reserved keywords, compared case sensitively.
+    Rust,
+    /// Common SQL reserved words, compared case insensitively.
+    Sql,
+}
+
+impl ReservedSet {
+    /// Rust strict and reserved keywords.
+    pub const RUST_KEYWORDS: &'static [&'static str] = &[
+        "Self", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+        "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if",
+        "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv",
+        "pub", "ref", "return", "self", "static", "struct", "super", "trait", "true", "try",
+        "type", "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+    ];
+    /// Common SQL reserved words, in lowercase.
+    pub const SQL_KEYWORDS: &'static [&'static str] = &[
+        "all",
+        "alter",
+        "and",
+        "any",
+        "as",
+        "asc",
+        "between",
+        "by",
+        "case",
+        "cast",
+        "check",
+        "column",
+        "constraint",
+        "create",
+        "cross",
+        "current",
+        "default",
+        "delete",
+        "desc",
+        "distinct",
+        "drop",
+        "else",
+        "end",
+        "except",
+        "exists",
+        "false",
+        "fetch",
+        "for",
+        "foreign",
+        "from",
+        "full",
+        "grant",
+        "group",
+        "having",
+        "in",
+        "inner",
+        "insert",
+        "intersect",
+        "into",
+        "is",
+        "join",
+        "key",
+        "left",
+        "like",
+        "limit",
+        "not",
+        "null",
+        "offset",
+        "on",
+        "or",
+        "order",
+        "outer",
+        "primary",
+        "references",
+        "right",
+        "select",
+        "set",
+        "table",
+        "then",
+        "to",
+        "true",
+        "union",
+        "unique",
+        "update",
+        "user",
+        "using",
+        "values",
+        "when",
+        "where",
+        "with",
+    ];
+
+    /// Returns the words in this set.
+    pub const fn words(&self) -> &'static [&'static str] {
+        match self {
+            Self::Rust => Self::RUST_KEYWORDS,
+            Self::Sql => Self::SQL_KEYWORDS,
+        }
+    }
+
+    /// Returns whether words in this set are compared case sensitively.
+    pub const fn is_case_sensitive(&self) -> bool {
+        match self {
+            Self::Rust => true,
+            Self::Sql => false,
+        }
+    }
+
+    /// Returns whether the given word is in this set.
+    pub const fn contains(&self, word: &str) -> bool {
+        let words = self.words();
+        let mut i = 0;
+        while i < words.len() {
+            let is_match = if self.is_case_sensitive() {
+                const_str::eq(words[i], word)
+            } else {
+                const_str::eq_ignore_ascii_case(words[i], word)
+            };
+            if is_match {
+                return true;
+            }
+            i += 1;
+        }
+        false
+    }
+}
+
+impl fmt::Display for ReservedSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rust => write!(f, "Rust keyword"),
+            Self::Sql => write!(f, "SQL keyword"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReservedSet;
+
+    #[test]
+    fn rust_is_case_sensitive() {
+        assert!(ReservedSet::Rust.contains("fn"));
+        assert!(ReservedSet::Rust.contains("Self"));
+        assert!(!ReservedSet::Rust.contains("FN"));
+        assert!(!ReservedSet::Rust.contains("function"));
+    }
+
+    #[test]
+    fn sql_is_case_insensitive() {
+        assert!(ReservedSet::Sql.contains("select"));
+        assert!(ReservedSet::Sql.contains("SELECT"));
+        assert!(ReservedSet::Sql.contains("Select"));
+        assert!(!ReservedSet::Sql.contains("selection"));
+    }
+}
@@ -0,0 +1,41 @@
+//! Small pseudo-random number generator for generating IDs.
+//!
+//! This is SplitMix64, which is fast and has no dependencies, but is not
+//! cryptographically secure.
+
+use std::hash::{BuildHasher, RandomState};
+
+/// SplitMix64 pseudo-random number generator.
+#[derive(Clone, Debug)]
+pub(crate) struct Rng {
+    /// State, which is advanced by a constant for each number.
+    state: u64,
+}
+
+impl Rng {
+    /// Returns a generator with the given seed, which always produces the
+    /// same numbers.
+    pub(crate) fn with_seed(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns a generator seeded from the random keys of a new
+    /// `RandomState`, which differ for each generator.
+    pub(crate) fn new() -> Self {
+        Self::with_seed(RandomState::new().hash_one(0u64))
+    }
+
+    /// Returns the next random `u64`.
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a random index less than `len`, which must not be zero.
+    pub(crate) fn index(&mut self, len: usize) -> usize {
+        ((u128::from(self.next_u64()) * len as u128) >> 64) as usize
+    }
+}
@@ -0,0 +1,46 @@
+use std::{error::Error, fmt};
+
+/// Error finding a free variant of an ID with [`IdNewtype::uniquify`], where
+/// `E` is the ID type's error.
+///
+/// [`IdNewtype::uniquify`]: crate::IdNewtype::uniquify
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UniquifyError<E> {
+    /// No free variant that was tried is valid, e.g. because the index
+    /// characters are not allowed. Holds the first free variant's error.
+    InvalidId(E),
+    /// Every variant that was tried is taken, or the index would overflow.
+    Exhausted {
+        /// The ID that variants were tried for.
+        value: String,
+        /// Number of variants that were tried.
+        attempts: usize,
+    },
+}
+
+impl<E> fmt::Display for UniquifyError<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidId(error) => write!(f, "No free variant is valid: {error}"),
+            Self::Exhausted { value, attempts } => write!(
+                f,
+                "No free variant of `{value}` was found after {attempts} attempts."
+            ),
+        }
+    }
+}
+
+impl<E> Error for UniquifyError<E>
+where
+    E: Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidId(error) => Some(error),
+            Self::Exhausted { .. } => None,
+        }
+    }
+}
@@ -0,0 +1,73 @@
+/// Iterator over the words of an ID, e.g. `web`, `Server`, `2` for
+/// `web_Server-2`.
+///
+/// Words are runs of ASCII letters and digits. They are separated by any
+/// other character, and by changes from lowercase letters or digits to
+/// uppercase letters. Acronyms are kept together, so `HTTPServer` is `HTTP`,
+/// `Server`.
+///
+/// ```rust
+/// use id_newtype::Words;
+///
+/// assert_eq!(
+///     vec!["web", "HTTP", "Server2", "Go"],
+///     Words::new("web_HTTPServer2Go").collect::<Vec<_>>()
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct Words<'s> {
+    /// Text after the last word that was returned.
+    rest: &'s str,
+}
+
+impl<'s> Words<'s> {
+    /// Returns an iterator over the words of the given `&str`.
+    pub fn new(s: &'s str) -> Self {
+        Self { rest: s }
+    }
+}
+
+impl<'s> Iterator for Words<'s> {
+    type Item = &'s str;
+
+    fn next(&mut self) -> Option<&'s str> {
+        let start = self.rest.find(|c: char| c.is_ascii_alphanumeric())?;
+        let bytes = self.rest.as_bytes();
+        // Non-ASCII bytes end the word, so `end` is always a `char` boundary.
+        let end = (start + 1..bytes.len())
+            .find(|end| {
+                let (prev, c) = (bytes[end - 1], bytes[*end]);
+                let next_is_lowercase = bytes.get(end + 1).is_some_and(u8::is_ascii_lowercase);
+                !c.is_ascii_alphanumeric()
+                    || (c.is_ascii_uppercase()
+                        && (prev.is_ascii_lowercase()
+                            || prev.is_ascii_digit()
+                            || (prev.is_ascii_uppercase() && next_is_lowercase)))
+            })
+            .unwrap_or(bytes.len());
+
+        let (word, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Some(&word[start..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Words;
+
+    fn words(s: &str) -> Vec<&str> {
+        Words::new(s).collect()
+    }
+
+    #[test]
+    fn splits_on_separators_and_case_changes() {
+        assert_eq!(vec!["web", "server"], words("web_server"));
+        assert_eq!(vec!["web", "Server"], words("webServer"));
+        assert_eq!(vec!["WEB", "SERVER"], words("__WEB-SERVER__"));
+        assert_eq!(vec!["HTTP", "Server"], words("HTTPServer"));
+        assert_eq!(vec!["v2", "Api"], words("v2Api"));
+        assert_eq!(vec!["a", "b"], words("a·b"));
+        assert!(words("__").is_empty());
+    }
+}